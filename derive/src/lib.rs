@@ -0,0 +1,141 @@
+//! # Derive macros for jaw
+//!
+//! `#[derive(Form)]` fills a struct field by field with `Input::ask`
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, Fields, LitInt, LitStr};
+
+/// implements `jaw::cli::Form` for a struct with named fields
+///
+/// every field is asked for in order and parsed with `FromStr`,
+/// the `form` attribute tunes a field:
+///
+/// - `prompt = "Port: "` the question, the field name by default
+/// - `id = "port"` the prompt id for answer sources, the field name by default
+/// - `default = "8080"` an answer used when nothing is typed
+/// - `validator = expr` a claim checked before parsing
+/// - `attempts = 5` attempts to type a valid answer
+#[proc_macro_derive(Form, attributes(form))]
+pub fn derive_form(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+struct FieldAttrs {
+    prompt: Option<LitStr>,
+    id: Option<LitStr>,
+    default: Option<LitStr>,
+    validator: Option<Expr>,
+    attempts: Option<LitInt>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "Form can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "Form can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut values = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named field");
+        let ty = &field.ty;
+        let attrs = field_attrs(field)?;
+
+        let text = match attrs.prompt {
+            Some(prompt) => quote!(#prompt),
+            None => {
+                let text = format!("{}: ", ident);
+                quote!(#text)
+            }
+        };
+        let id = match attrs.id {
+            Some(id) => quote!(#id),
+            None => {
+                let id = ident.to_string();
+                quote!(#id)
+            }
+        };
+        let default = attrs.default.map(|default| quote!(.default(#default)));
+        let parse = quote!(|s: &str| s.trim().parse::<#ty>());
+        let claim = match attrs.validator {
+            Some(validator) => quote!(::jaw::validators::and(#validator, #parse)),
+            None => parse,
+        };
+        let attempts = match attrs.attempts {
+            Some(attempts) => quote!(Some(#attempts)),
+            None => quote!(None),
+        };
+        values.push(quote! {
+            #ident: input.ask(
+                ::jaw::cli::Prompt::new(#text).id(#id)#default,
+                #claim,
+                #attempts,
+            )?
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::jaw::cli::Form for #name #ty_generics #where_clause {
+            fn fill<R, W>(
+                input: &::jaw::cli::Input<R, W>,
+            ) -> ::std::result::Result<Self, ::jaw::cli::InputReadError>
+            where
+                R: ::jaw::cli::Reader,
+                W: ::jaw::cli::Writer,
+            {
+                ::std::result::Result::Ok(#name {
+                    #(#values,)*
+                })
+            }
+        }
+    })
+}
+
+fn field_attrs(field: &syn::Field) -> syn::Result<FieldAttrs> {
+    let mut attrs = FieldAttrs {
+        prompt: None,
+        id: None,
+        default: None,
+        validator: None,
+        attempts: None,
+    };
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("form")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("prompt") {
+                attrs.prompt = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("id") {
+                attrs.id = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("default") {
+                attrs.default = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("validator") {
+                attrs.validator = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("attempts") {
+                attrs.attempts = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("unknown form attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(attrs)
+}
@@ -0,0 +1,206 @@
+//! # Answer sources
+//!
+//! answers to prompts with ids given without asking anybody,
+//! e.g. to run a tool in a non interactive environment
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// knows answers to prompts by their ids
+pub trait AnswerSource {
+    fn answer(&self, id: &str) -> Option<String>;
+
+    /// a name of the place the answer is taken from,
+    /// used in error messages
+    fn name(&self, id: &str) -> String;
+}
+
+/// answers from environment variables
+///
+/// the id `port` with the prefix `MYTOOL` is looked up as `MYTOOL_PORT`
+pub struct EnvAnswers {
+    prefix: String,
+}
+
+impl EnvAnswers {
+    pub fn new(prefix: &str) -> EnvAnswers {
+        EnvAnswers {
+            prefix: prefix.to_string(),
+        }
+    }
+
+    /// a variable name for the id, upper cased with
+    /// anything but letters and digits replaced by `_`
+    pub fn var_name(&self, id: &str) -> String {
+        let id: String = id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        if self.prefix.is_empty() {
+            id
+        } else {
+            format!("{}_{}", self.prefix, id)
+        }
+    }
+}
+
+impl AnswerSource for EnvAnswers {
+    fn answer(&self, id: &str) -> Option<String> {
+        env::var(self.var_name(id)).ok()
+    }
+
+    fn name(&self, id: &str) -> String {
+        format!("environment variable {}", self.var_name(id))
+    }
+}
+
+/// what happens to a prompt no answer source knows
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MissingAnswer {
+    /// the prompt is asked through the reader
+    #[default]
+    Ask,
+    /// the prompt fails with `ErrorKind::MissingAnswer`
+    Fail,
+}
+
+/// answers recorded in a file, keyed by prompt ids
+///
+/// `.json` and `.toml` files need the `json` and `toml` features,
+/// any other file is read as `id=value` lines where empty lines
+/// and lines starting with `#` are skipped, nested json objects
+/// and toml tables give ids like `server.port`
+pub struct AnswerFile {
+    path: PathBuf,
+    answers: HashMap<String, String>,
+}
+
+impl AnswerFile {
+    pub fn open<P>(path: P) -> Result<AnswerFile, io::Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        let answers = match path.extension().and_then(|ext| ext.to_str()) {
+            #[cfg(feature = "json")]
+            Some("json") => parse_json(&text)?,
+            #[cfg(feature = "toml")]
+            Some("toml") => parse_toml(&text)?,
+            _ => parse_pairs(&text)?,
+        };
+        Ok(AnswerFile {
+            path: path.to_path_buf(),
+            answers,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AnswerSource for AnswerFile {
+    fn answer(&self, id: &str) -> Option<String> {
+        self.answers.get(id).cloned()
+    }
+
+    fn name(&self, id: &str) -> String {
+        format!("answer '{}' in {}", id, self.path.display())
+    }
+}
+
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// parses `id=value` lines
+pub(crate) fn parse_pairs(text: &str) -> Result<HashMap<String, String>, io::Error> {
+    let mut answers = HashMap::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((id, value)) = line.split_once('=') else {
+            return Err(invalid_data(format!(
+                "line {}: expected id=value, found '{}'",
+                n + 1,
+                line
+            )));
+        };
+        answers.insert(id.trim().to_string(), value.trim().to_string());
+    }
+    Ok(answers)
+}
+
+#[cfg(feature = "json")]
+fn parse_json(text: &str) -> Result<HashMap<String, String>, io::Error> {
+    fn flatten(prefix: &str, value: &serde_json::Value, answers: &mut HashMap<String, String>) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, value) in map {
+                    flatten(&join(prefix, key), value, answers);
+                }
+            }
+            serde_json::Value::String(s) => {
+                answers.insert(prefix.to_string(), s.clone());
+            }
+            serde_json::Value::Null => {
+                answers.insert(prefix.to_string(), String::new());
+            }
+            value => {
+                answers.insert(prefix.to_string(), value.to_string());
+            }
+        }
+    }
+    let value: serde_json::Value =
+        serde_json::from_str(text).map_err(|err| invalid_data(err.to_string()))?;
+    if !value.is_object() {
+        return Err(invalid_data("expected a json object".to_string()));
+    }
+    let mut answers = HashMap::new();
+    flatten("", &value, &mut answers);
+    Ok(answers)
+}
+
+#[cfg(feature = "toml")]
+fn parse_toml(text: &str) -> Result<HashMap<String, String>, io::Error> {
+    fn flatten(prefix: &str, value: &toml::Value, answers: &mut HashMap<String, String>) {
+        match value {
+            toml::Value::Table(table) => {
+                for (key, value) in table {
+                    flatten(&join(prefix, key), value, answers);
+                }
+            }
+            toml::Value::String(s) => {
+                answers.insert(prefix.to_string(), s.clone());
+            }
+            value => {
+                answers.insert(prefix.to_string(), value.to_string());
+            }
+        }
+    }
+    let table: toml::Table = toml::from_str(text).map_err(|err| invalid_data(err.to_string()))?;
+    let mut answers = HashMap::new();
+    flatten("", &toml::Value::Table(table), &mut answers);
+    Ok(answers)
+}
+
+#[cfg(any(feature = "json", feature = "toml"))]
+fn join(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
@@ -0,0 +1,193 @@
+//! # Async cli
+//!
+//! counterparts of `cli::Reader` and `cli::Input` for tokio based tools,
+//! available with the `async` feature
+
+use std::fmt::{Debug, Display};
+use std::future::Future;
+use std::io;
+
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, BufReader, Stdin};
+use tokio::sync::Mutex;
+
+use crate::cli::{wrong_input, InputReadError, Normalization};
+
+pub trait AsyncReader {
+    /// reads a line
+    ///
+    /// a closed input is reported with `io::ErrorKind::UnexpectedEof`
+    /// rather than an empty string
+    fn read_string(&self) -> impl Future<Output = Result<String, io::Error>> + Send;
+}
+
+/// reads lines from any `AsyncBufRead`
+pub struct AsyncLines<R> {
+    inner: Mutex<R>,
+}
+
+impl<R> AsyncLines<R>
+where
+    R: AsyncBufRead + Unpin + Send,
+{
+    pub fn new(inner: R) -> AsyncLines<R> {
+        AsyncLines {
+            inner: Mutex::new(inner),
+        }
+    }
+}
+
+impl<R> AsyncReader for AsyncLines<R>
+where
+    R: AsyncBufRead + Unpin + Send,
+{
+    async fn read_string(&self) -> Result<String, io::Error> {
+        let mut buf = String::new();
+        if self.inner.lock().await.read_line(&mut buf).await? == 0 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "end of input"));
+        }
+        Ok(buf)
+    }
+}
+
+/// lines of the process standard input
+pub fn stdin() -> AsyncLines<BufReader<Stdin>> {
+    AsyncLines::new(BufReader::new(tokio::io::stdin()))
+}
+
+/// receives a claim error and the number of attempts left,
+/// like `cli::Feedback::Callback` but it can be shared by tasks
+pub type AsyncFeedbackFn = Box<dyn Fn(&str, u8) + Send + Sync>;
+
+pub struct AsyncInput<T>
+where
+    T: AsyncReader,
+{
+    reader: T,
+    normalization: Normalization,
+    feedback: Option<AsyncFeedbackFn>,
+}
+
+impl<T> AsyncInput<T>
+where
+    T: AsyncReader,
+{
+    pub fn new(reader: T) -> AsyncInput<T> {
+        AsyncInput {
+            reader,
+            normalization: Normalization::default(),
+            feedback: None,
+        }
+    }
+
+    /// sets how read strings are cleaned up
+    pub fn with_normalization(mut self, normalization: Normalization) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
+    /// sets a callback rejected attempts of `demand_until` are reported to,
+    /// without it they are not reported
+    pub fn with_feedback<F>(mut self, feedback: F) -> Self
+    where
+        F: Fn(&str, u8) + Send + Sync + 'static,
+    {
+        self.feedback = Some(Box::new(feedback));
+        self
+    }
+
+    pub fn reader(&self) -> &T {
+        &self.reader
+    }
+
+    /// reads a string and normalizes it
+    pub async fn read(&self) -> Result<String, InputReadError> {
+        let inp = self.reader.read_string().await?;
+        Ok(self.normalization.apply(inp))
+    }
+
+    pub async fn demand<F, Ft, Fe>(&self, claim: F) -> Result<String, InputReadError>
+    where
+        F: Fn(&str) -> Result<Ft, Fe>,
+        Fe: Display + Debug,
+    {
+        let inp = self.read().await?;
+        match claim(&inp) {
+            Ok(_) => Ok(inp),
+            Err(err) => Err(wrong_input(err, None)),
+        }
+    }
+
+    /// like `demand` but returns the value produced by the claim
+    pub async fn demand_value<F, Ft, Fe>(&self, claim: F) -> Result<Ft, InputReadError>
+    where
+        F: Fn(&str) -> Result<Ft, Fe>,
+        Fe: Display + Debug,
+    {
+        let inp = self.read().await?;
+        claim(&inp).map_err(|err| wrong_input(err, None))
+    }
+
+    /// read and check
+    ///
+    /// reads an input and passes it to the predicate
+    /// until a predicate isn't positive
+    pub async fn demand_until<F, Ft, Fe>(
+        &self,
+        claim: F,
+        attempts: Option<u8>,
+    ) -> Result<String, InputReadError>
+    where
+        F: Fn(&str) -> Result<Ft, Fe>,
+        Fe: Display + Debug,
+    {
+        self.claim_until(&claim, attempts).await.map(|(inp, _)| inp)
+    }
+
+    /// like `demand_until` but returns the value produced by the claim
+    pub async fn demand_value_until<F, Ft, Fe>(
+        &self,
+        claim: F,
+        attempts: Option<u8>,
+    ) -> Result<Ft, InputReadError>
+    where
+        F: Fn(&str) -> Result<Ft, Fe>,
+        Fe: Display + Debug,
+    {
+        self.claim_until(&claim, attempts)
+            .await
+            .map(|(_, value)| value)
+    }
+
+    async fn claim_until<F, Ft, Fe>(
+        &self,
+        claim: &F,
+        attempts: Option<u8>,
+    ) -> Result<(String, Ft), InputReadError>
+    where
+        F: Fn(&str) -> Result<Ft, Fe>,
+        Fe: Display + Debug,
+    {
+        let attempts = attempts.unwrap_or(3u8);
+        let mut errors = Vec::new();
+        for attempt in 1..=attempts {
+            let inp = self.read().await?;
+            match claim(&inp) {
+                Ok(value) => return Ok((inp, value)),
+                Err(err) => {
+                    let msg = err.to_string();
+                    if attempt < attempts {
+                        self.report(&msg, attempts - attempt);
+                    }
+                    errors.push(msg);
+                }
+            }
+        }
+        Err(InputReadError::attempts_exceeded(errors))
+    }
+
+    fn report(&self, msg: &str, attempts_left: u8) {
+        if let Some(feedback) = &self.feedback {
+            feedback(msg, attempts_left);
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! # Completion
+//!
+//! completers suggest how to finish the typed text,
+//! the line editor asks them when tab is pressed
+
+use std::fmt::Debug;
+use std::fs;
+use std::path::Path;
+
+/// candidates to replace the text from `start` up to the cursor with
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Completion {
+    /// a byte offset in the text before the cursor
+    pub start: usize,
+    pub candidates: Vec<String>,
+}
+
+/// suggests completions of the text before the cursor
+///
+/// closures taking the text and returning a `Completion`
+/// are completers too, which comes in handy in tests
+pub trait Completer {
+    fn complete(&self, line: &str) -> Completion;
+}
+
+impl<F> Completer for F
+where
+    F: Fn(&str) -> Completion,
+{
+    fn complete(&self, line: &str) -> Completion {
+        self(line)
+    }
+}
+
+impl Debug for dyn Completer + '_ {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Completer")
+    }
+}
+
+/// completes the last word from a fixed list
+pub struct Words {
+    words: Vec<String>,
+}
+
+impl Words {
+    pub fn new<I, S>(words: I) -> Words
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Words {
+            words: words.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl Completer for Words {
+    fn complete(&self, line: &str) -> Completion {
+        let start = word_start(line);
+        let word = &line[start..];
+        Completion {
+            start,
+            candidates: self
+                .words
+                .iter()
+                .filter(|w| w.starts_with(word))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// completes the last word as a filesystem path,
+/// directories end with `/`
+///
+/// hidden entries are suggested once the name starts with `.`
+#[derive(Default)]
+pub struct Paths;
+
+impl Completer for Paths {
+    fn complete(&self, line: &str) -> Completion {
+        let start = word_start(line);
+        let word = &line[start..];
+        let (dir, name) = match word.rfind('/') {
+            Some(i) => (&word[..=i], &word[i + 1..]),
+            None => ("", word),
+        };
+        let mut candidates = Vec::new();
+        let read_from = if dir.is_empty() { "." } else { dir };
+        if let Ok(entries) = fs::read_dir(Path::new(read_from)) {
+            for entry in entries.flatten() {
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                if !file_name.starts_with(name)
+                    || file_name.starts_with('.') && !name.starts_with('.')
+                {
+                    continue;
+                }
+                let is_dir = entry.path().is_dir();
+                candidates.push(format!(
+                    "{}{}{}",
+                    dir,
+                    file_name,
+                    if is_dir { "/" } else { "" }
+                ));
+            }
+        }
+        candidates.sort();
+        Completion { start, candidates }
+    }
+}
+
+/// completes the whole line with option labels,
+/// the case of the typed text doesn't matter like in `select`
+pub struct Options {
+    labels: Vec<String>,
+}
+
+impl Options {
+    pub fn new<I, S>(labels: I) -> Options
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Options {
+            labels: labels.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl Completer for Options {
+    fn complete(&self, line: &str) -> Completion {
+        let typed = line.trim_start().to_lowercase();
+        Completion {
+            start: 0,
+            candidates: self
+                .labels
+                .iter()
+                .filter(|l| l.to_lowercase().starts_with(&typed))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// the longest beginning all candidates share
+pub fn common_prefix(candidates: &[String]) -> String {
+    let Some((first, rest)) = candidates.split_first() else {
+        return String::new();
+    };
+    let mut len = first.len();
+    for candidate in rest {
+        len = first
+            .char_indices()
+            .zip(candidate.chars())
+            .take_while(|((i, a), b)| *i < len && a == b)
+            .map(|((i, a), _)| i + a.len_utf8())
+            .last()
+            .unwrap_or(0);
+    }
+    first[..len].to_string()
+}
+
+fn word_start(line: &str) -> usize {
+    line.rfind(char::is_whitespace)
+        .map(|i| i + line[i..].chars().next().map_or(1, char::len_utf8))
+        .unwrap_or(0)
+}
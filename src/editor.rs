@@ -0,0 +1,706 @@
+//! # Line editor
+//!
+//! an interactive `Reader` for terminals with cursor movement
+//! and emacs like editing keys, when stdin isn't a terminal
+//! lines are read as usual
+
+use std::cell::{Ref, RefCell};
+use std::io::{self, stdin, stdout, Read, Stdin};
+use std::time::{Duration, Instant};
+
+use crate::cli::{read_stdin, Prompt, Reader, Writer};
+use crate::completion::{common_prefix, Completer};
+use crate::fuzzy::{fuzzy_filter, Match};
+use crate::hints::{HintContext, Hinter};
+use crate::history::History;
+use crate::term::{self, RawGuard, StdinBytes};
+
+/// a decoded key press
+#[derive(Debug, Clone, PartialEq)]
+pub enum Key {
+    Char(char),
+    Enter,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    WordLeft,
+    WordRight,
+    Home,
+    End,
+    Up,
+    Down,
+    Tab,
+    /// ctrl-k
+    KillToEnd,
+    /// ctrl-u
+    KillToStart,
+    /// ctrl-w
+    KillWordBack,
+    /// ctrl-y
+    Yank,
+    /// ctrl-c
+    Interrupt,
+    /// ctrl-d
+    EndOfInput,
+    Unknown,
+}
+
+fn read_byte<R>(input: &mut R) -> Result<u8, io::Error>
+where
+    R: Read,
+{
+    let mut byte = [0u8];
+    loop {
+        match input.read(&mut byte) {
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(_) => return Ok(byte[0]),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// reads one key press from raw terminal bytes
+pub fn read_key<R>(input: &mut R) -> Result<Key, io::Error>
+where
+    R: Read,
+{
+    let key = match read_byte(input)? {
+        0x01 => Key::Home,
+        0x02 => Key::Left,
+        0x03 => Key::Interrupt,
+        0x04 => Key::EndOfInput,
+        0x05 => Key::End,
+        0x06 => Key::Right,
+        0x08 | 0x7f => Key::Backspace,
+        0x09 => Key::Tab,
+        0x0a | 0x0d => Key::Enter,
+        0x0b => Key::KillToEnd,
+        0x0e => Key::Down,
+        0x10 => Key::Up,
+        0x15 => Key::KillToStart,
+        0x17 => Key::KillWordBack,
+        0x19 => Key::Yank,
+        0x1b => read_escape(input)?,
+        byte if byte < 0x20 => Key::Unknown,
+        byte => read_char(byte, input)?,
+    };
+    Ok(key)
+}
+
+fn read_escape<R>(input: &mut R) -> Result<Key, io::Error>
+where
+    R: Read,
+{
+    let key = match read_byte(input)? {
+        b'[' => {
+            let mut params = Vec::new();
+            let last = loop {
+                match read_byte(input)? {
+                    byte @ 0x40..=0x7e => break byte,
+                    byte => params.push(byte),
+                }
+            };
+            // modified arrows look like `1;5C`
+            let modified = params.contains(&b';');
+            match (last, &params[..]) {
+                (b'A', _) => Key::Up,
+                (b'B', _) => Key::Down,
+                (b'C', _) if modified => Key::WordRight,
+                (b'D', _) if modified => Key::WordLeft,
+                (b'C', _) => Key::Right,
+                (b'D', _) => Key::Left,
+                (b'H', _) | (b'~', b"1") | (b'~', b"7") => Key::Home,
+                (b'F', _) | (b'~', b"4") | (b'~', b"8") => Key::End,
+                (b'~', b"3") => Key::Delete,
+                _ => Key::Unknown,
+            }
+        }
+        b'O' => match read_byte(input)? {
+            b'A' => Key::Up,
+            b'B' => Key::Down,
+            b'C' => Key::Right,
+            b'D' => Key::Left,
+            b'H' => Key::Home,
+            b'F' => Key::End,
+            _ => Key::Unknown,
+        },
+        b'b' => Key::WordLeft,
+        b'f' => Key::WordRight,
+        _ => Key::Unknown,
+    };
+    Ok(key)
+}
+
+fn read_char<R>(first: u8, input: &mut R) -> Result<Key, io::Error>
+where
+    R: Read,
+{
+    let len = match first {
+        0x00..=0x7f => 1,
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => return Ok(Key::Unknown),
+    };
+    let mut bytes = vec![first];
+    for _ in 1..len {
+        bytes.push(read_byte(input)?);
+    }
+    Ok(match std::str::from_utf8(&bytes) {
+        Ok(s) => s.chars().next().map(Key::Char).unwrap_or(Key::Unknown),
+        Err(_) => Key::Unknown,
+    })
+}
+
+/// an edited line and a cursor in it
+#[derive(Debug, Default)]
+pub struct LineBuffer {
+    chars: Vec<char>,
+    cursor: usize,
+    killed: Vec<char>,
+}
+
+impl LineBuffer {
+    pub fn text(&self) -> String {
+        self.chars.iter().collect()
+    }
+
+    /// the cursor position in characters
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    /// replaces the text and puts the cursor at the end
+    pub fn set(&mut self, text: &str) {
+        self.chars = text.chars().collect();
+        self.cursor = self.chars.len();
+    }
+
+    /// applies an editing key, `false` if the key doesn't edit
+    pub fn apply(&mut self, key: &Key) -> bool {
+        match key {
+            Key::Char(c) => {
+                self.chars.insert(self.cursor, *c);
+                self.cursor += 1;
+            }
+            Key::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.chars.remove(self.cursor);
+            }
+            Key::Delete if self.cursor < self.chars.len() => {
+                self.chars.remove(self.cursor);
+            }
+            Key::Left => self.cursor = self.cursor.saturating_sub(1),
+            Key::Right => self.cursor = (self.cursor + 1).min(self.chars.len()),
+            Key::Home => self.cursor = 0,
+            Key::End => self.cursor = self.chars.len(),
+            Key::WordLeft => self.cursor = self.word_start(),
+            Key::WordRight => self.cursor = self.word_end(),
+            Key::KillToEnd => {
+                self.killed = self.chars.split_off(self.cursor);
+            }
+            Key::KillToStart => {
+                self.killed = self.chars.drain(..self.cursor).collect();
+                self.cursor = 0;
+            }
+            Key::KillWordBack => {
+                let start = self.word_start();
+                self.killed = self.chars.drain(start..self.cursor).collect();
+                self.cursor = start;
+            }
+            Key::Yank => {
+                let killed = self.killed.clone();
+                let len = killed.len();
+                self.chars.splice(self.cursor..self.cursor, killed);
+                self.cursor += len;
+            }
+            Key::Backspace | Key::Delete => {}
+            _ => return false,
+        }
+        true
+    }
+
+    /// the start of the word before the cursor
+    fn word_start(&self) -> usize {
+        let mut i = self.cursor;
+        while i > 0 && !self.chars[i - 1].is_alphanumeric() {
+            i -= 1;
+        }
+        while i > 0 && self.chars[i - 1].is_alphanumeric() {
+            i -= 1;
+        }
+        i
+    }
+
+    /// the end of the word after the cursor
+    fn word_end(&self) -> usize {
+        let mut i = self.cursor;
+        let len = self.chars.len();
+        while i < len && !self.chars[i].is_alphanumeric() {
+            i += 1;
+        }
+        while i < len && self.chars[i].is_alphanumeric() {
+            i += 1;
+        }
+        i
+    }
+}
+
+/// draws a line after the prompt, assuming it fits the terminal width
+#[derive(Default)]
+struct View {
+    /// where the cursor is drawn, in characters from the line start
+    cursor: usize,
+}
+
+impl View {
+    /// a hint is drawn dimmed after the line
+    fn render<W>(&mut self, line: &LineBuffer, hint: Option<&str>, out: &W) -> Result<(), io::Error>
+    where
+        W: Writer + ?Sized,
+    {
+        let mut screen = String::new();
+        if self.cursor > 0 {
+            screen.push_str(&format!("\x1b[{}D", self.cursor));
+        }
+        screen.push_str(&line.text());
+        let hint = hint.unwrap_or_default();
+        if !hint.is_empty() {
+            screen.push_str(&format!("\x1b[2m{}\x1b[22m", hint));
+        }
+        screen.push_str("\x1b[K");
+        let back = line.chars.len() - line.cursor + hint.chars().count();
+        if back > 0 {
+            screen.push_str(&format!("\x1b[{}D", back));
+        }
+        self.cursor = line.cursor;
+        out.write_string(&screen)
+    }
+}
+
+/// what a line is edited with
+#[derive(Default)]
+pub(crate) struct Session<'a> {
+    /// the written prompt, drawn again after listing completions
+    pub prompt: String,
+    pub history: &'a [String],
+    pub completer: Option<&'a dyn Completer>,
+    pub hinter: Option<&'a dyn Hinter>,
+    pub default: Option<&'a str>,
+    pub ignore_interrupts: bool,
+}
+
+impl Session<'_> {
+    /// hints are shown only while the cursor is at the end
+    fn hint(&self, line: &LineBuffer) -> Option<String> {
+        let hinter = self.hinter?;
+        if line.cursor < line.chars.len() {
+            return None;
+        }
+        let context = HintContext {
+            default: self.default,
+            history: self.history,
+        };
+        hinter.hint(&line.text(), &context)
+    }
+}
+
+/// edits a line with keys until enter is pressed
+///
+/// the line is returned with `\n` like a usual read,
+/// ctrl-c fails with `io::ErrorKind::Interrupted` unless interrupts
+/// are ignored and ctrl-d on an empty line closes the input,
+/// up and down walk through the history, the oldest entry first,
+/// right arrow at the end of the line accepts the hint
+pub(crate) fn edit<K, W>(mut next_key: K, out: &W, session: &Session) -> Result<String, io::Error>
+where
+    K: FnMut() -> Result<Key, io::Error>,
+    W: Writer + ?Sized,
+{
+    let history = session.history;
+    let mut line = LineBuffer::default();
+    let mut view = View::default();
+    // the shown history entry, `history.len()` is the typed line
+    let mut shown = history.len();
+    let mut typed = String::new();
+    let mut hint = session.hint(&line);
+    if hint.is_some() {
+        view.render(&line, hint.as_deref(), out)?;
+    }
+    loop {
+        match next_key()? {
+            Key::Right if hint.is_some() => {
+                line.set(&(line.text() + hint.as_deref().unwrap_or_default()));
+            }
+            Key::Up if shown > 0 => {
+                if shown == history.len() {
+                    typed = line.text();
+                }
+                shown -= 1;
+                line.set(&history[shown]);
+            }
+            Key::Down if shown < history.len() => {
+                shown += 1;
+                match history.get(shown) {
+                    Some(entry) => line.set(entry),
+                    None => line.set(&typed),
+                }
+            }
+            Key::Tab => {
+                let Some(completer) = session.completer else {
+                    continue;
+                };
+                if let Some(listed) = complete(&mut line, completer) {
+                    out.write_string(&format!("\r\n{}\r\n{}", listed, session.prompt))?;
+                    view = View::default();
+                }
+            }
+            Key::Enter => {
+                if hint.is_some() {
+                    view.render(&line, None, out)?;
+                }
+                out.write_string("\r\n")?;
+                return Ok(line.text() + "\n");
+            }
+            Key::Interrupt if session.ignore_interrupts => continue,
+            Key::Interrupt => {
+                out.write_string("\r\n")?;
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "interrupted"));
+            }
+            Key::EndOfInput if line.is_empty() => {
+                out.write_string("\r\n")?;
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "end of input"));
+            }
+            Key::EndOfInput => {
+                line.apply(&Key::Delete);
+            }
+            key => {
+                if !line.apply(&key) {
+                    continue;
+                }
+            }
+        }
+        hint = session.hint(&line);
+        view.render(&line, hint.as_deref(), out)?;
+    }
+}
+
+/// completes the text before the cursor as far as candidates agree,
+/// gives the candidates to list if it can't go any further
+fn complete(line: &mut LineBuffer, completer: &dyn Completer) -> Option<String> {
+    let before: String = line.chars[..line.cursor].iter().collect();
+    let completion = completer.complete(&before);
+    let start = before
+        .get(..completion.start)
+        .map_or(0, |s| s.chars().count());
+    let typed = line.cursor - start;
+    let prefix = match &completion.candidates[..] {
+        [] => return None,
+        [candidate] => candidate.clone(),
+        candidates => common_prefix(candidates),
+    };
+    if prefix.chars().count() > typed || completion.candidates.len() == 1 {
+        line.chars.splice(start..line.cursor, prefix.chars());
+        line.cursor = start + prefix.chars().count();
+        return None;
+    }
+    Some(completion.candidates.join("  "))
+}
+
+/// matches shown at once by the picker
+const PICKER_HEIGHT: usize = 10;
+
+/// draws the typed query with the best matches below it
+#[derive(Default)]
+struct PickerView {
+    /// lines drawn below the query
+    lines: usize,
+}
+
+impl PickerView {
+    fn render<W>(
+        &mut self,
+        prompt: &str,
+        query: &LineBuffer,
+        labels: &[String],
+        found: &[(usize, Match)],
+        selected: usize,
+        out: &W,
+    ) -> Result<(), io::Error>
+    where
+        W: Writer + ?Sized,
+    {
+        let mut screen = String::from("\r");
+        screen.push_str(prompt);
+        screen.push_str(&query.text());
+        screen.push_str(&format!(
+            "  \x1b[2m{}/{}\x1b[22m\x1b[K",
+            found.len(),
+            labels.len()
+        ));
+        // the selected match stays visible when scrolling down
+        let top = (selected + 1).saturating_sub(PICKER_HEIGHT);
+        let shown = &found[top..found.len().min(top + PICKER_HEIGHT)];
+        for (i, (index, m)) in shown.iter().enumerate() {
+            let marker = if top + i == selected { "> " } else { "  " };
+            screen.push_str("\r\n");
+            screen.push_str(marker);
+            screen.push_str(&highlight(&labels[*index], &m.positions));
+            screen.push_str("\x1b[K");
+        }
+        screen.push_str("\x1b[J");
+        if !shown.is_empty() {
+            screen.push_str(&format!("\x1b[{}A", shown.len()));
+        }
+        screen.push_str(&format!(
+            "\r\x1b[{}C",
+            prompt.chars().count() + query.cursor()
+        ));
+        self.lines = shown.len();
+        out.write_string(&screen)
+    }
+
+    /// leaves the prompt and the answer
+    fn finish<W>(&self, prompt: &str, answer: &str, out: &W) -> Result<(), io::Error>
+    where
+        W: Writer + ?Sized,
+    {
+        out.write_string(&format!("\r{}{}\x1b[K\x1b[J\r\n", prompt, answer))
+    }
+}
+
+/// matched characters are bold
+fn highlight(label: &str, positions: &[usize]) -> String {
+    let mut text = String::new();
+    for (i, c) in label.chars().enumerate() {
+        if positions.contains(&i) {
+            text.push_str(&format!("\x1b[1m{}\x1b[22m", c));
+        } else {
+            text.push(c);
+        }
+    }
+    text
+}
+
+/// picks a label typing a part of it
+///
+/// the labels are filtered and ordered by `fuzzy_filter` as the
+/// query changes, up and down (ctrl-p/ctrl-n, tab) move the selection
+/// and enter picks the selected label
+pub(crate) fn pick<K, W>(
+    mut next_key: K,
+    out: &W,
+    prompt: &str,
+    labels: &[String],
+    ignore_interrupts: bool,
+) -> Result<usize, io::Error>
+where
+    K: FnMut() -> Result<Key, io::Error>,
+    W: Writer + ?Sized,
+{
+    let mut query = LineBuffer::default();
+    let mut view = PickerView::default();
+    let mut found = fuzzy_filter("", labels);
+    let mut selected = 0;
+    loop {
+        view.render(prompt, &query, labels, &found, selected, out)?;
+        let key = match next_key() {
+            Ok(key) => key,
+            Err(err) => {
+                view.finish(prompt, &query.text(), out)?;
+                return Err(err);
+            }
+        };
+        match key {
+            Key::Up => selected = selected.saturating_sub(1),
+            Key::Down | Key::Tab => selected = (selected + 1).min(found.len().saturating_sub(1)),
+            Key::Enter => {
+                if let Some((index, _)) = found.get(selected) {
+                    view.finish(prompt, &labels[*index], out)?;
+                    return Ok(*index);
+                }
+            }
+            Key::Interrupt if ignore_interrupts => {}
+            Key::Interrupt => {
+                view.finish(prompt, &query.text(), out)?;
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "interrupted"));
+            }
+            Key::EndOfInput if query.is_empty() => {
+                view.finish(prompt, "", out)?;
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "end of input"));
+            }
+            Key::EndOfInput => {
+                query.apply(&Key::Delete);
+            }
+            key => {
+                let before = query.text();
+                query.apply(&key);
+                if query.text() != before {
+                    found = fuzzy_filter(&query.text(), labels);
+                    selected = 0;
+                }
+            }
+        }
+    }
+}
+
+/// a `Reader` editing lines in the terminal
+///
+/// left/right, home/end (ctrl-a/ctrl-e), word jumps (alt-b/alt-f,
+/// ctrl-left/ctrl-right), ctrl-k/ctrl-u/ctrl-w to kill and ctrl-y
+/// to yank the killed text back
+///
+/// with a history, answers to prompts with ids are stored
+/// and recalled with up and down, tab completes the answer
+/// with the prompt's completer, `Input::fuzzy_select` gets
+/// a fuzzy finder and a hinter suggests the rest of the line
+///
+/// answers to prompts are drawn to the writer of the `Input`,
+/// reads without a prompt are drawn to stdout
+pub struct LineEditor {
+    stdin: Stdin,
+    history: Option<RefCell<History>>,
+    hinter: Option<Box<dyn Hinter>>,
+}
+
+impl LineEditor {
+    pub fn new() -> LineEditor {
+        LineEditor {
+            stdin: stdin(),
+            history: None,
+            hinter: None,
+        }
+    }
+
+    /// shows hints while typing, e.g. `hints::Suggestions`
+    pub fn with_hinter<H>(mut self, hinter: H) -> Self
+    where
+        H: Hinter + 'static,
+    {
+        self.hinter = Some(Box::new(hinter));
+        self
+    }
+
+    /// keeps answers in the history
+    pub fn with_history(mut self, history: History) -> Self {
+        self.history = Some(RefCell::new(history));
+        self
+    }
+
+    pub fn history(&self) -> Option<Ref<'_, History>> {
+        self.history.as_ref().map(RefCell::borrow)
+    }
+
+    fn read_line(
+        &self,
+        session: &Session,
+        timeout: Option<Duration>,
+        out: &dyn Writer,
+    ) -> Result<String, io::Error> {
+        if !term::is_tty() {
+            return read_stdin(&self.stdin, timeout, session.ignore_interrupts);
+        }
+        let _raw = RawGuard::new()?;
+        let mut bytes = StdinBytes;
+        edit(
+            until(timeout, session.ignore_interrupts, || read_key(&mut bytes)),
+            out,
+            session,
+        )
+    }
+}
+
+/// keys read before the timeout passes, counted from the first key
+fn until<K>(
+    timeout: Option<Duration>,
+    ignore_interrupts: bool,
+    mut next_key: K,
+) -> impl FnMut() -> Result<Key, io::Error>
+where
+    K: FnMut() -> Result<Key, io::Error>,
+{
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    move || {
+        let left = deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        if !term::wait_readable(left, ignore_interrupts)? {
+            return Err(term::timed_out(timeout.unwrap_or_default()));
+        }
+        next_key()
+    }
+}
+
+impl Default for LineEditor {
+    fn default() -> Self {
+        LineEditor::new()
+    }
+}
+
+impl Reader for LineEditor {
+    fn read_string(&self) -> Result<String, io::Error> {
+        self.read_line(&Session::default(), None, &stdout())
+    }
+
+    fn read_secret(&self) -> Result<String, io::Error> {
+        self.stdin.read_secret()
+    }
+
+    fn read_prompted(&self, prompt: &Prompt, out: &dyn Writer) -> Result<String, io::Error> {
+        let entries = match (&self.history, prompt.get_id()) {
+            (Some(history), Some(id)) if prompt.keeps_history() => {
+                history.borrow().entries(id).to_vec()
+            }
+            _ => Vec::new(),
+        };
+        let session = Session {
+            prompt: prompt.render(),
+            history: &entries,
+            completer: prompt.get_completer(),
+            hinter: self.hinter.as_deref(),
+            default: prompt.get_default(),
+            ignore_interrupts: prompt.ignores_interrupts(),
+        };
+        self.read_line(&session, prompt.get_timeout(), out)
+    }
+
+    fn read_string_timeout(&self, timeout: Duration) -> Result<String, io::Error> {
+        self.read_line(&Session::default(), Some(timeout), &stdout())
+    }
+
+    fn pick(
+        &self,
+        prompt: &Prompt,
+        labels: &[String],
+        out: &dyn Writer,
+    ) -> Option<Result<usize, io::Error>> {
+        if !term::is_tty() {
+            return None;
+        }
+        let picked = RawGuard::new().and_then(|_raw| {
+            let mut bytes = StdinBytes;
+            pick(
+                until(prompt.get_timeout(), prompt.ignores_interrupts(), || {
+                    read_key(&mut bytes)
+                }),
+                out,
+                &prompt.render(),
+                labels,
+                prompt.ignores_interrupts(),
+            )
+        });
+        Some(picked)
+    }
+
+    /// the history is a convenience, an answer that can't be saved
+    /// doesn't fail the prompt
+    fn remember(&self, prompt: &Prompt, answer: &str) {
+        if let (Some(history), Some(id)) = (&self.history, prompt.get_id()) {
+            let _ = history.borrow_mut().add(id, answer);
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! # Fuzzy matching
+//!
+//! scores how well typed characters match a text,
+//! the characters have to appear in order but not side by side
+
+/// a matched text
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    /// higher is better
+    pub score: i64,
+    /// indexes of matched characters in the text
+    pub positions: Vec<usize>,
+}
+
+const MATCHED: i64 = 16;
+const CONSECUTIVE: i64 = 12;
+const WORD_START: i64 = 8;
+const GAP: i64 = 1;
+
+/// matches the pattern against the text ignoring case
+///
+/// consecutive characters and characters starting words score
+/// higher, every skipped character costs a bit, an empty pattern
+/// matches anything
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<Match> {
+    let pattern: Vec<char> = pattern.chars().map(lowercase).collect();
+    let chars: Vec<char> = text.chars().collect();
+    let lowered: Vec<char> = chars.iter().copied().map(lowercase).collect();
+    let Some(&first) = pattern.first() else {
+        return Some(Match {
+            score: 0,
+            positions: Vec::new(),
+        });
+    };
+    // every place the first character matches is tried
+    // and the best scoring run is kept
+    let mut best: Option<Match> = None;
+    for start in (0..chars.len()).filter(|&i| lowered[i] == first) {
+        let Some(positions) = positions_from(&pattern, &lowered, start) else {
+            break;
+        };
+        let score = score(&chars, &positions);
+        if best.as_ref().is_none_or(|b| score > b.score) {
+            best = Some(Match { score, positions });
+        }
+    }
+    best
+}
+
+/// matches the pattern against every label, the best first
+///
+/// gives indexes of matched labels with their matches
+pub fn fuzzy_filter<S>(pattern: &str, labels: &[S]) -> Vec<(usize, Match)>
+where
+    S: AsRef<str>,
+{
+    let mut found: Vec<(usize, Match)> = labels
+        .iter()
+        .enumerate()
+        .filter_map(|(i, label)| fuzzy_match(pattern, label.as_ref()).map(|m| (i, m)))
+        .collect();
+    // the sort is stable so equal scores keep the order of labels
+    found.sort_by_key(|(_, m)| std::cmp::Reverse(m.score));
+    found
+}
+
+fn positions_from(pattern: &[char], lowered: &[char], start: usize) -> Option<Vec<usize>> {
+    let mut positions = vec![start];
+    let mut i = start + 1;
+    for &p in &pattern[1..] {
+        while i < lowered.len() && lowered[i] != p {
+            i += 1;
+        }
+        if i == lowered.len() {
+            return None;
+        }
+        positions.push(i);
+        i += 1;
+    }
+    Some(positions)
+}
+
+fn score(chars: &[char], positions: &[usize]) -> i64 {
+    let mut score = 0;
+    let mut previous: Option<usize> = None;
+    for &i in positions {
+        score += MATCHED;
+        if is_word_start(chars, i) {
+            score += WORD_START;
+        }
+        match previous {
+            Some(p) if p + 1 == i => score += CONSECUTIVE,
+            Some(p) => score -= GAP * (i - p - 1) as i64,
+            None => score -= GAP * i as i64,
+        }
+        previous = Some(i);
+    }
+    score
+}
+
+fn lowercase(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn is_word_start(chars: &[char], i: usize) -> bool {
+    if i == 0 {
+        return true;
+    }
+    let (before, at) = (chars[i - 1], chars[i]);
+    !before.is_alphanumeric() && at.is_alphanumeric() || before.is_lowercase() && at.is_uppercase()
+}
@@ -0,0 +1,73 @@
+//! # Hints
+//!
+//! suggestions shown dimmed after the typed text in the line editor,
+//! right arrow at the end of the line accepts them
+
+/// what a hint can be made of
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HintContext<'a> {
+    /// the prompt's default answer
+    pub default: Option<&'a str>,
+    /// previous answers to the prompt, the oldest first
+    pub history: &'a [String],
+}
+
+/// suggests how the typed line goes on
+///
+/// a hint is the text to show after the line, closures taking
+/// the line and the context are hinters too
+pub trait Hinter {
+    fn hint(&self, line: &str, context: &HintContext) -> Option<String>;
+}
+
+impl<F> Hinter for F
+where
+    F: Fn(&str, &HintContext) -> Option<String>,
+{
+    fn hint(&self, line: &str, context: &HintContext) -> Option<String> {
+        self(line, context)
+    }
+}
+
+/// the rest of the default answer
+#[derive(Default)]
+pub struct DefaultHinter;
+
+impl Hinter for DefaultHinter {
+    fn hint(&self, line: &str, context: &HintContext) -> Option<String> {
+        rest(context.default?, line)
+    }
+}
+
+/// the rest of the most recent answer starting with the line
+#[derive(Default)]
+pub struct HistoryHinter;
+
+impl Hinter for HistoryHinter {
+    fn hint(&self, line: &str, context: &HintContext) -> Option<String> {
+        context
+            .history
+            .iter()
+            .rev()
+            .find_map(|entry| rest(entry, line))
+    }
+}
+
+/// the history first, then the default
+#[derive(Default)]
+pub struct Suggestions;
+
+impl Hinter for Suggestions {
+    fn hint(&self, line: &str, context: &HintContext) -> Option<String> {
+        HistoryHinter
+            .hint(line, context)
+            .or_else(|| DefaultHinter.hint(line, context))
+    }
+}
+
+fn rest(suggestion: &str, line: &str) -> Option<String> {
+    suggestion
+        .strip_prefix(line)
+        .filter(|rest| !rest.is_empty())
+        .map(str::to_string)
+}
@@ -0,0 +1,141 @@
+//! # History
+//!
+//! previous answers to prompts with ids, kept between runs
+//! in the XDG state directory
+
+use std::collections::HashMap;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// entries kept per prompt unless set otherwise
+pub const DEFAULT_LIMIT: usize = 100;
+
+/// answers by prompt ids, the oldest first
+///
+/// an answer given again moves to the end instead of being
+/// stored twice and only the last `limit` answers of a prompt
+/// are kept, the file is written as `id<TAB>answer` lines
+#[derive(Debug, Default)]
+pub struct History {
+    entries: HashMap<String, Vec<String>>,
+    path: Option<PathBuf>,
+    limit: Option<usize>,
+}
+
+impl History {
+    /// a history kept only while the program runs
+    pub fn new() -> History {
+        History::default()
+    }
+
+    /// a history of the app in `$XDG_STATE_HOME/<app>/history`,
+    /// `~/.local/state` is used when the variable isn't set
+    pub fn open(app: &str) -> Result<History, io::Error> {
+        let dir = state_dir().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "no state directory for history")
+        })?;
+        History::at(dir.join(app).join("history"))
+    }
+
+    /// a history stored in the file, which is created on the first answer
+    pub fn at<P>(path: P) -> Result<History, io::Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_path_buf();
+        let mut history = History::default();
+        match fs::read_to_string(&path) {
+            Ok(text) => {
+                for line in text.lines() {
+                    if let Some((id, entry)) = line.split_once('\t') {
+                        history.push(id, entry);
+                    }
+                }
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+        history.path = Some(path);
+        Ok(history)
+    }
+
+    /// sets how many answers are kept per prompt
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        for entries in self.entries.values_mut() {
+            trim(entries, limit);
+        }
+        self
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// answers to the prompt, the oldest first
+    pub fn entries(&self, id: &str) -> &[String] {
+        self.entries.get(id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// adds an answer and saves the file
+    ///
+    /// empty and multi line answers aren't stored
+    pub fn add(&mut self, id: &str, entry: &str) -> Result<(), io::Error> {
+        if self.push(id, entry) {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, id: &str, entry: &str) -> bool {
+        let entry = entry.trim_end_matches(['\r', '\n']);
+        if entry.trim().is_empty() || entry.contains('\n') || id.contains(['\t', '\n']) {
+            return false;
+        }
+        let limit = self.limit.unwrap_or(DEFAULT_LIMIT);
+        let entries = self.entries.entry(id.to_string()).or_default();
+        entries.retain(|e| e != entry);
+        entries.push(entry.to_string());
+        trim(entries, limit);
+        true
+    }
+
+    fn save(&self) -> Result<(), io::Error> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut ids: Vec<_> = self.entries.keys().collect();
+        ids.sort();
+        let mut text = String::new();
+        for id in ids {
+            for entry in &self.entries[id] {
+                text.push_str(&format!("{}\t{}\n", id, entry));
+            }
+        }
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        // answers are nobody else's business
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        options.open(path)?.write_all(text.as_bytes())
+    }
+}
+
+fn trim(entries: &mut Vec<String>, limit: usize) {
+    if entries.len() > limit {
+        entries.drain(..entries.len() - limit);
+    }
+}
+
+/// `$XDG_STATE_HOME` or `~/.local/state`
+pub fn state_dir() -> Option<PathBuf> {
+    match env::var_os("XDG_STATE_HOME").map(PathBuf::from) {
+        Some(dir) if dir.is_absolute() => Some(dir),
+        _ => env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/state")),
+    }
+}
@@ -6,11 +6,95 @@
 pub mod cli {
 
     use std::fmt::{Debug, Display};
-    use std::io::{self};
-    use std::io::{stdin, Stdin};
+    use std::io::{self, Write};
+    use std::io::{stdin, stdout, Stderr, Stdin, Stdout};
+    use std::str::FromStr;
+    use std::time::Duration;
+    #[cfg(feature = "unicode")]
+    use unicode_normalization::UnicodeNormalization;
+    use zeroize::Zeroizing;
+
+    use crate::answers::{AnswerSource, EnvAnswers, MissingAnswer};
+    use crate::completion::{Completer, Options};
+    use crate::term::{self, EchoGuard, InterruptGuard};
 
     pub trait Reader {
+        /// reads a line
+        ///
+        /// a closed input is reported with `io::ErrorKind::UnexpectedEof`
+        /// rather than an empty string
         fn read_string(&self) -> Result<String, io::Error>;
+
+        /// reads a string that must not be shown while typed
+        ///
+        /// readers attached to a terminal should turn echo off,
+        /// the rest may read as usual
+        fn read_secret(&self) -> Result<String, io::Error> {
+            self.read_string()
+        }
+
+        /// reads an answer to the prompt, which is already written to `out`
+        ///
+        /// readers that care which question is answered, e.g. to
+        /// record it, look at the prompt, readers drawing the answer
+        /// draw it to `out`, the rest may read as usual
+        fn read_prompted(&self, prompt: &Prompt, out: &dyn Writer) -> Result<String, io::Error> {
+            let _ = out;
+            match prompt.timeout {
+                Some(timeout) => self.read_string_timeout(timeout),
+                None => self.read_string(),
+            }
+        }
+
+        /// like `read_string` but gives up after the timeout
+        /// with `io::ErrorKind::TimedOut`
+        ///
+        /// readers that can't wait for a limited time read as usual
+        fn read_string_timeout(&self, timeout: Duration) -> Result<String, io::Error> {
+            let _ = timeout;
+            self.read_string()
+        }
+
+        /// called when the prompt timed out and its default is taken
+        /// instead of an answer read
+        ///
+        /// readers recording answers record the default,
+        /// so a replay gives it to the same prompt
+        fn timed_out(&self, prompt: &Prompt, default: &str) -> Result<(), io::Error> {
+            let _ = (prompt, default);
+            Ok(())
+        }
+
+        /// called with an answer typed to the prompt once it's accepted
+        ///
+        /// readers keeping a history of answers store it,
+        /// secrets and prompts with `no_history` never get here
+        fn remember(&self, prompt: &Prompt, answer: &str) {
+            let _ = (prompt, answer);
+        }
+
+        /// lets the user pick one of the labels interactively, e.g. with
+        /// a fuzzy finder, and gives the index of the picked one
+        ///
+        /// the reader shows the prompt itself on `out`, readers that can't
+        /// pick give `None` and a numbered list is asked instead
+        fn pick(
+            &self,
+            prompt: &Prompt,
+            labels: &[String],
+            out: &dyn Writer,
+        ) -> Option<Result<usize, io::Error>> {
+            let _ = (prompt, labels, out);
+            None
+        }
+    }
+
+    /// output counterpart of `Reader`
+    ///
+    /// a written string has to be visible right away,
+    /// so implementations flush after writing
+    pub trait Writer {
+        fn write_string(&self, s: &str) -> Result<(), io::Error>;
     }
 
     #[derive(Debug, PartialEq)]
@@ -18,18 +102,53 @@ pub mod cli {
         IoError,
         AttemptsExceedError,
         InputRequirementError,
+        /// the input is closed, nothing more can be read
+        EndOfInput,
+        /// a user gave up answering, e.g. left a wizard
+        Cancelled,
+        /// no answer source knows the answer and asking isn't allowed
+        MissingAnswer,
+        /// nothing was typed in time
+        Timeout,
+        /// a user pressed ctrl-c
+        Interrupted,
     }
 
     #[derive(Debug)]
     pub struct InputReadError {
         msg: String,
         kind: ErrorKind,
+        attempt_errors: Vec<String>,
     }
 
     impl InputReadError {
+        pub(crate) fn new(msg: String, kind: ErrorKind) -> InputReadError {
+            InputReadError {
+                msg,
+                kind,
+                attempt_errors: Vec::new(),
+            }
+        }
+
+        pub(crate) fn attempts_exceeded(errors: Vec<String>) -> InputReadError {
+            let last_error_msg = errors.last().cloned().unwrap_or_default();
+            InputReadError {
+                msg: format!("attempts to read input were failed. {}", last_error_msg),
+                kind: ErrorKind::AttemptsExceedError,
+                attempt_errors: errors,
+            }
+        }
+
         pub fn kind(&self) -> &ErrorKind {
             &self.kind
         }
+
+        /// claim errors of every failed attempt, in order
+        ///
+        /// empty unless the kind is `AttemptsExceedError`
+        pub fn attempt_errors(&self) -> &[String] {
+            &self.attempt_errors
+        }
     }
 
     impl Display for InputReadError {
@@ -40,10 +159,13 @@ pub mod cli {
 
     impl From<io::Error> for InputReadError {
         fn from(value: io::Error) -> Self {
-            InputReadError {
-                msg: value.to_string(),
-                kind: ErrorKind::IoError,
-            }
+            let kind = match value.kind() {
+                io::ErrorKind::UnexpectedEof => ErrorKind::EndOfInput,
+                io::ErrorKind::TimedOut => ErrorKind::Timeout,
+                io::ErrorKind::Interrupted => ErrorKind::Interrupted,
+                _ => ErrorKind::IoError,
+            };
+            InputReadError::new(value.to_string(), kind)
         }
     }
 
@@ -57,18 +179,369 @@ pub mod cli {
         fn demand_until(&self, claim: F, attempts: Option<u8>) -> Result<String, InputReadError>;
     }
 
-    pub struct Input<T>
+    /// words accepted as answers by `Input::confirm`
+    ///
+    /// the first word of each list is shown in the prompt hint
+    pub struct ConfirmWords {
+        yes: Vec<String>,
+        no: Vec<String>,
+    }
+
+    impl ConfirmWords {
+        /// both lists need at least one word
+        pub fn new(yes: &[&str], no: &[&str]) -> ConfirmWords {
+            assert!(
+                !yes.is_empty() && !no.is_empty(),
+                "confirm words can't be empty"
+            );
+            ConfirmWords {
+                yes: yes.iter().map(|w| w.to_lowercase()).collect(),
+                no: no.iter().map(|w| w.to_lowercase()).collect(),
+            }
+        }
+
+        /// matches an answer against the words ignoring case,
+        /// `None` if the answer is neither
+        pub fn parse(&self, answer: &str) -> Option<bool> {
+            let answer = answer.trim().to_lowercase();
+            if self.yes.contains(&answer) {
+                Some(true)
+            } else if self.no.contains(&answer) {
+                Some(false)
+            } else {
+                None
+            }
+        }
+
+        /// hint like `[y/N]` with the default capitalized
+        fn hint(&self, default: Option<bool>) -> String {
+            let (yes, no) = (&self.yes[0], &self.no[0]);
+            match default {
+                Some(true) => format!("[{}/{}]", yes.to_uppercase(), no),
+                Some(false) => format!("[{}/{}]", yes, no.to_uppercase()),
+                None => format!("[{}/{}]", yes, no),
+            }
+        }
+    }
+
+    impl Default for ConfirmWords {
+        fn default() -> Self {
+            ConfirmWords::new(&["y", "yes"], &["n", "no"])
+        }
+    }
+
+    /// receives a claim error and the number of attempts left
+    pub type FeedbackFn = Box<dyn Fn(&str, u8)>;
+
+    /// how a rejected attempt of `demand_until` is reported
+    /// before the input is read again
+    ///
+    /// unless it's set with `Input::with_feedback`, rejected answers
+    /// to prompts are reported to the writer and plain demands
+    /// without a prompt report nothing
+    pub enum Feedback {
+        /// the claim error and the number of attempts left
+        /// are written to the input's writer
+        Writer,
+        /// nothing is reported
+        Silent,
+        /// the claim error and the number of attempts left
+        /// are passed to the callback
+        Callback(FeedbackFn),
+    }
+
+    /// how a read string is cleaned up before it's checked or returned
+    ///
+    /// by default only the line terminator is removed
+    #[derive(Debug, Clone)]
+    pub struct Normalization {
+        trim_line_end: bool,
+        trim_whitespace: bool,
+        #[cfg(feature = "unicode")]
+        nfc: bool,
+    }
+
+    impl Normalization {
+        /// leaves a string as it was read
+        pub fn none() -> Normalization {
+            Normalization {
+                trim_line_end: false,
+                trim_whitespace: false,
+                #[cfg(feature = "unicode")]
+                nfc: false,
+            }
+        }
+
+        /// removes a trailing `\n` or `\r\n`
+        pub fn trim_line_end(mut self, on: bool) -> Self {
+            self.trim_line_end = on;
+            self
+        }
+
+        /// removes leading and trailing whitespace
+        pub fn trim_whitespace(mut self, on: bool) -> Self {
+            self.trim_whitespace = on;
+            self
+        }
+
+        /// brings a string to the unicode normalization form C
+        #[cfg(feature = "unicode")]
+        pub fn nfc(mut self, on: bool) -> Self {
+            self.nfc = on;
+            self
+        }
+
+        pub fn apply(&self, mut s: String) -> String {
+            if self.trim_line_end {
+                if s.ends_with('\n') {
+                    s.pop();
+                }
+                if s.ends_with('\r') {
+                    s.pop();
+                }
+            }
+            if self.trim_whitespace && s.trim().len() != s.len() {
+                s = s.trim().to_string();
+            }
+            #[cfg(feature = "unicode")]
+            if self.nfc {
+                s = s.nfc().collect();
+            }
+            s
+        }
+    }
+
+    impl Default for Normalization {
+        fn default() -> Self {
+            Normalization::none().trim_line_end(true)
+        }
+    }
+
+    /// a question written before reading an answer
+    #[derive(Debug, Clone)]
+    pub struct Prompt<'a> {
+        text: &'a str,
+        id: Option<&'a str>,
+        default: Option<&'a str>,
+        timeout: Option<Duration>,
+        ignore_interrupts: bool,
+        history: bool,
+        completer: Option<&'a dyn Completer>,
+    }
+
+    impl<'a> Prompt<'a> {
+        pub fn new(text: &'a str) -> Prompt<'a> {
+            Prompt {
+                text,
+                id: None,
+                default: None,
+                timeout: None,
+                ignore_interrupts: false,
+                history: true,
+                completer: None,
+            }
+        }
+
+        /// a name the answer can be looked up by in answer sources
+        pub fn id(mut self, id: &'a str) -> Self {
+            self.id = Some(id);
+            self
+        }
+
+        /// an answer used when nothing is typed
+        ///
+        /// it's shown in the question like `Port [8080]: `
+        pub fn default(mut self, default: &'a str) -> Self {
+            self.default = Some(default);
+            self
+        }
+
+        /// how long to wait for an answer, the default is given
+        /// if nothing is typed in time and there is one
+        pub fn timeout(mut self, timeout: Duration) -> Self {
+            self.timeout = Some(timeout);
+            self
+        }
+
+        /// keeps waiting for an answer when ctrl-c is pressed
+        /// instead of failing with `ErrorKind::Interrupted`
+        pub fn ignore_interrupts(mut self) -> Self {
+            self.ignore_interrupts = true;
+            self
+        }
+
+        /// keeps answers out of the history, e.g. for sensitive data
+        pub fn no_history(mut self) -> Self {
+            self.history = false;
+            self
+        }
+
+        /// completes the answer on tab in the line editor
+        pub fn completer(mut self, completer: &'a dyn Completer) -> Self {
+            self.completer = Some(completer);
+            self
+        }
+
+        pub fn text(&self) -> &str {
+            self.text
+        }
+
+        pub fn get_id(&self) -> Option<&str> {
+            self.id
+        }
+
+        pub fn get_default(&self) -> Option<&'a str> {
+            self.default
+        }
+
+        pub fn get_timeout(&self) -> Option<Duration> {
+            self.timeout
+        }
+
+        pub fn ignores_interrupts(&self) -> bool {
+            self.ignore_interrupts
+        }
+
+        pub fn keeps_history(&self) -> bool {
+            self.history
+        }
+
+        pub fn get_completer(&self) -> Option<&'a dyn Completer> {
+            self.completer
+        }
+
+        /// the same prompt with another question
+        fn with_text<'b>(&self, text: &'b str) -> Prompt<'b>
+        where
+            'a: 'b,
+        {
+            Prompt {
+                text,
+                ..self.clone()
+            }
+        }
+
+        /// the question as it's written
+        pub fn render(&self) -> String {
+            let Some(default) = self.default else {
+                return self.text.to_string();
+            };
+            let text = self.text.trim_end();
+            match text.strip_suffix(':') {
+                Some(text) => format!("{} [{}]: ", text, default),
+                None => format!("{} [{}] ", text, default),
+            }
+        }
+    }
+
+    impl<'a> From<&'a str> for Prompt<'a> {
+        fn from(text: &'a str) -> Self {
+            Prompt::new(text)
+        }
+    }
+
+    /// a secret read from an input
+    ///
+    /// the memory is wiped on drop and the value is never
+    /// shown by `Debug`
+    pub struct Secret(Zeroizing<String>);
+
+    impl Secret {
+        fn new(mut value: String) -> Secret {
+            let len = value.trim_end_matches(['\r', '\n']).len();
+            value.truncate(len);
+            Secret(Zeroizing::new(value))
+        }
+
+        pub fn expose(&self) -> &str {
+            &self.0
+        }
+    }
+
+    impl PartialEq for Secret {
+        fn eq(&self, other: &Self) -> bool {
+            self.expose() == other.expose()
+        }
+    }
+
+    impl Debug for Secret {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "Secret(***)")
+        }
+    }
+
+    pub struct Input<T, W = Stdout>
     where
         T: Reader,
+        W: Writer,
     {
         reader: T,
+        writer: W,
+        feedback: Option<Feedback>,
+        confirm_words: ConfirmWords,
+        normalization: Normalization,
+        sources: Vec<Box<dyn AnswerSource>>,
+        missing_answer: MissingAnswer,
+        timeout: Option<Duration>,
+        completer: Option<Box<dyn Completer>>,
     }
 
+    /// ctrl-c while waiting for a line from a terminal or with a timeout
+    /// gives `io::ErrorKind::Interrupted` instead of killing the process,
+    /// such reads take SIGINT over from the application while they wait
+    /// unless it's ignored
+    ///
+    /// such reads take bytes from the descriptor one by one, a line
+    /// already buffered by `Stdin`, e.g. after reading `stdin()`
+    /// directly, isn't seen by them, other reads go through `Stdin`
     impl Reader for Stdin {
         fn read_string(&self) -> Result<String, io::Error> {
+            read_stdin(self, None, false)
+        }
+
+        fn read_secret(&self) -> Result<String, io::Error> {
+            let _echo = EchoGuard::new()?;
+            self.read_string()
+        }
+
+        fn read_prompted(&self, prompt: &Prompt, _out: &dyn Writer) -> Result<String, io::Error> {
+            read_stdin(self, prompt.timeout, prompt.ignore_interrupts)
+        }
+
+        fn read_string_timeout(&self, timeout: Duration) -> Result<String, io::Error> {
+            read_stdin(self, Some(timeout), false)
+        }
+    }
+
+    pub(crate) fn read_stdin(
+        stdin: &Stdin,
+        timeout: Option<Duration>,
+        ignore_interrupts: bool,
+    ) -> Result<String, io::Error> {
+        if timeout.is_none() && !term::is_tty() {
             let mut buf = String::new();
-            self.read_line(&mut buf)?;
-            Ok(buf)
+            if stdin.read_line(&mut buf)? == 0 {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "end of input"));
+            }
+            return Ok(buf);
+        }
+        let _interrupts = InterruptGuard::new()?;
+        term::read_line(timeout, ignore_interrupts)
+    }
+
+    impl Writer for Stdout {
+        fn write_string(&self, s: &str) -> Result<(), io::Error> {
+            let mut out = self.lock();
+            out.write_all(s.as_bytes())?;
+            out.flush()
+        }
+    }
+
+    impl Writer for Stderr {
+        fn write_string(&self, s: &str) -> Result<(), io::Error> {
+            let mut out = self.lock();
+            out.write_all(s.as_bytes())?;
+            out.flush()
         }
     }
 
@@ -77,37 +550,609 @@ pub mod cli {
         T: Reader,
     {
         pub fn new(reader: T) -> Input<T> {
-            Input { reader }
+            Input {
+                reader,
+                writer: stdout(),
+                feedback: None,
+                confirm_words: ConfirmWords::default(),
+                normalization: Normalization::default(),
+                sources: Vec::new(),
+                missing_answer: MissingAnswer::default(),
+                timeout: None,
+                completer: None,
+            }
+        }
+    }
+
+    impl<T, W> Input<T, W>
+    where
+        T: Reader,
+        W: Writer,
+    {
+        /// replaces the output sink prompts are written to
+        pub fn with_writer<V>(self, writer: V) -> Input<T, V>
+        where
+            V: Writer,
+        {
+            Input {
+                reader: self.reader,
+                writer,
+                feedback: self.feedback,
+                confirm_words: self.confirm_words,
+                normalization: self.normalization,
+                sources: self.sources,
+                missing_answer: self.missing_answer,
+                timeout: self.timeout,
+                completer: self.completer,
+            }
+        }
+
+        /// completes answers to prompts without a completer of their own
+        pub fn with_completer<C>(mut self, completer: C) -> Self
+        where
+            C: Completer + 'static,
+        {
+            self.completer = Some(Box::new(completer));
+            self
+        }
+
+        /// sets how rejected attempts are reported
+        pub fn with_feedback(mut self, feedback: Feedback) -> Self {
+            self.feedback = Some(feedback);
+            self
+        }
+
+        /// sets how read strings are cleaned up
+        pub fn with_normalization(mut self, normalization: Normalization) -> Self {
+            self.normalization = normalization;
+            self
+        }
+
+        /// adds a source of answers consulted before the reader,
+        /// sources are tried in the order they were added
+        pub fn with_source<S>(mut self, source: S) -> Self
+        where
+            S: AnswerSource + 'static,
+        {
+            self.sources.push(Box::new(source));
+            self
         }
 
+        /// answers prompts with ids from environment variables
+        /// like `PREFIX_ID`
+        pub fn with_env_prefix(self, prefix: &str) -> Self {
+            self.with_source(EnvAnswers::new(prefix))
+        }
+
+        /// sets what happens to a prompt no answer source knows
+        pub fn with_missing_answer(mut self, missing_answer: MissingAnswer) -> Self {
+            self.missing_answer = missing_answer;
+            self
+        }
+
+        /// sets how long to wait for every read,
+        /// a prompt may set its own timeout
+        pub fn with_timeout(mut self, timeout: Duration) -> Self {
+            self.timeout = Some(timeout);
+            self
+        }
+
+        /// reads a string and normalizes it
         pub fn read(&self) -> Result<String, InputReadError> {
-            let inp = self.reader.read_string()?;
+            let inp = match self.timeout {
+                Some(timeout) => self.reader.read_string_timeout(timeout)?,
+                None => self.reader.read_string()?,
+            };
+            Ok(self.normalization.apply(inp))
+        }
+
+        /// like `read` but gives `None` when the input is closed
+        pub fn read_opt(&self) -> Result<Option<String>, InputReadError> {
+            match self.read() {
+                Ok(inp) => Ok(Some(inp)),
+                Err(err) if err.kind == ErrorKind::EndOfInput => Ok(None),
+                Err(err) => Err(err),
+            }
+        }
+
+        /// like `read` but gives the default on an empty input
+        pub fn read_or(&self, default: &str) -> Result<String, InputReadError> {
+            let inp = self.read()?;
+            Ok(if inp.is_empty() {
+                default.to_string()
+            } else {
+                inp
+            })
+        }
+
+        /// ask and read
+        ///
+        /// writes the question and reads the answer on the same line,
+        /// a plain string is written as is
+        ///
+        /// a prompt with an id is answered by the first answer source
+        /// that knows the id, nothing is written then
+        pub fn prompt<'p>(&self, prompt: impl Into<Prompt<'p>>) -> Result<String, InputReadError> {
+            let prompt = prompt.into();
+            let (inp, source) = self.fetch(Some(&prompt))?;
+            self.remember(Some(&prompt), &inp, &source);
             Ok(inp)
         }
 
+        /// ask and check
+        ///
+        /// asks the question until the answer satisfies the claim
+        /// and returns the value produced by the claim,
+        /// a default is passed through the claim as well
+        pub fn ask<'p, F, Ft, Fe>(
+            &self,
+            prompt: impl Into<Prompt<'p>>,
+            claim: F,
+            attempts: Option<u8>,
+        ) -> Result<Ft, InputReadError>
+        where
+            F: Fn(&str) -> Result<Ft, Fe>,
+            Fe: Display + Debug,
+        {
+            self.claim_until(Some(&prompt.into()), &claim, attempts)
+                .map(|(_, value)| value)
+        }
+
+        /// sets words `confirm` accepts, e.g. for another language
+        pub fn with_confirm_words(mut self, words: ConfirmWords) -> Self {
+            self.confirm_words = words;
+            self
+        }
+
+        /// ask a yes/no question
+        ///
+        /// the question is followed by a hint like `[y/N]`,
+        /// an empty answer gives the default if there is one
+        pub fn confirm<'p>(
+            &self,
+            prompt: impl Into<Prompt<'p>>,
+            default: Option<bool>,
+        ) -> Result<bool, InputReadError> {
+            let prompt = prompt.into();
+            let question = self.confirm_question(prompt.text, default);
+            self.claim(Some(&prompt.with_text(&question)), &|s: &str| {
+                self.parse_confirm(s, default)
+            })
+            .map(|(_, value)| value)
+        }
+
+        /// like `confirm` but asks again on a wrong answer
+        pub fn confirm_until<'p>(
+            &self,
+            prompt: impl Into<Prompt<'p>>,
+            default: Option<bool>,
+            attempts: Option<u8>,
+        ) -> Result<bool, InputReadError> {
+            let prompt = prompt.into();
+            let question = self.confirm_question(prompt.text, default);
+            self.claim_until(
+                Some(&prompt.with_text(&question)),
+                &|s: &str| self.parse_confirm(s, default),
+                attempts,
+            )
+            .map(|(_, value)| value)
+        }
+
+        /// pick one of the items
+        ///
+        /// writes the items as a numbered list and asks the question,
+        /// an answer is either a number from the list or an unambiguous
+        /// beginning of an item's label
+        pub fn select<'a, 'p, I>(
+            &self,
+            prompt: impl Into<Prompt<'p>>,
+            items: &'a [I],
+            attempts: Option<u8>,
+        ) -> Result<&'a I, InputReadError>
+        where
+            I: Display,
+        {
+            self.select_index(prompt, items, attempts)
+                .map(|index| &items[index])
+        }
+
+        /// like `select` but returns the index of the chosen item
+        ///
+        /// labels of the items are completed on tab in the line editor
+        pub fn select_index<'p, I>(
+            &self,
+            prompt: impl Into<Prompt<'p>>,
+            items: &[I],
+            attempts: Option<u8>,
+        ) -> Result<usize, InputReadError>
+        where
+            I: Display,
+        {
+            let mut prompt = prompt.into();
+            let labels = self.write_options(&prompt, items)?;
+            let options = Options::new(labels.iter().cloned());
+            prompt.completer = prompt.completer.or(Some(&options));
+            self.claim_until(Some(&prompt), &|s: &str| match_option(s, &labels), attempts)
+                .map(|(_, index)| index)
+        }
+
+        /// pick one of many items by typing a part of it
+        ///
+        /// readers that can, like the line editor on a terminal, filter
+        /// the items live while typing, otherwise it's `select`
+        pub fn fuzzy_select<'a, 'p, I>(
+            &self,
+            prompt: impl Into<Prompt<'p>>,
+            items: &'a [I],
+            attempts: Option<u8>,
+        ) -> Result<&'a I, InputReadError>
+        where
+            I: Display,
+        {
+            self.fuzzy_select_index(prompt, items, attempts)
+                .map(|index| &items[index])
+        }
+
+        /// like `fuzzy_select` but returns the index of the chosen item
+        ///
+        /// answer sources, the missing answer policy and the timeout
+        /// apply as to any prompt, a default is picked on a timeout
+        pub fn fuzzy_select_index<'p, I>(
+            &self,
+            prompt: impl Into<Prompt<'p>>,
+            items: &[I],
+            attempts: Option<u8>,
+        ) -> Result<usize, InputReadError>
+        where
+            I: Display,
+        {
+            let mut prompt = prompt.into();
+            let labels: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+            if labels.is_empty() || self.source_answer(&prompt).is_some() {
+                return self.select_index(prompt, items, attempts);
+            }
+            self.check_missing(&prompt)?;
+            prompt.timeout = prompt.timeout.or(self.timeout);
+            match self.reader.pick(&prompt, &labels, &self.writer) {
+                Some(Ok(index)) => Ok(index),
+                Some(Err(err)) if err.kind() == io::ErrorKind::TimedOut => match prompt.default {
+                    Some(default) => {
+                        self.reader.timed_out(&prompt, default)?;
+                        match_option(default, &labels).map_err(|err| wrong_input(err, None))
+                    }
+                    None => Err(err.into()),
+                },
+                Some(Err(err)) => Err(err.into()),
+                None => self.select_index(prompt, items, attempts),
+            }
+        }
+
+        /// pick several of the items
+        ///
+        /// writes the items as a numbered list and asks the question,
+        /// an answer is a list like `1,3,5-9`, `all` or `none`,
+        /// `!4` excludes an item, e.g. `all,!4`
+        pub fn multi_select<'a, 'p, I>(
+            &self,
+            prompt: impl Into<Prompt<'p>>,
+            items: &'a [I],
+        ) -> Result<Vec<&'a I>, InputReadError>
+        where
+            I: Display,
+        {
+            let prompt = prompt.into();
+            self.write_options(&prompt, items)?;
+            self.claim(Some(&prompt), &|s: &str| parse_selection(s, items.len()))
+                .map(|(_, indexes)| indexes.into_iter().map(|i| &items[i]).collect())
+        }
+
+        /// like `multi_select` but asks again on a wrong answer
+        pub fn multi_select_until<'a, 'p, I>(
+            &self,
+            prompt: impl Into<Prompt<'p>>,
+            items: &'a [I],
+            attempts: Option<u8>,
+        ) -> Result<Vec<&'a I>, InputReadError>
+        where
+            I: Display,
+        {
+            let prompt = prompt.into();
+            self.write_options(&prompt, items)?;
+            self.claim_until(
+                Some(&prompt),
+                &|s: &str| parse_selection(s, items.len()),
+                attempts,
+            )
+            .map(|(_, indexes)| indexes.into_iter().map(|i| &items[i]).collect())
+        }
+
+        /// writes items as a numbered list and returns their labels,
+        /// nothing is written if an answer source answers the prompt
+        fn write_options<I>(
+            &self,
+            prompt: &Prompt,
+            items: &[I],
+        ) -> Result<Vec<String>, InputReadError>
+        where
+            I: Display,
+        {
+            if items.is_empty() {
+                return Err(InputReadError::new(
+                    "there are no options to choose from".to_string(),
+                    ErrorKind::InputRequirementError,
+                ));
+            }
+            let labels: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+            if self.source_answer(prompt).is_some() {
+                return Ok(labels);
+            }
+            let width = labels.len().to_string().len();
+            let mut list = String::new();
+            for (i, label) in labels.iter().enumerate() {
+                list.push_str(&format!("{:>width$}) {}\n", i + 1, label, width = width));
+            }
+            self.writer.write_string(&list)?;
+            Ok(labels)
+        }
+
+        fn confirm_question(&self, question: &str, default: Option<bool>) -> String {
+            format!(
+                "{} {} ",
+                question.trim_end(),
+                self.confirm_words.hint(default)
+            )
+        }
+
+        fn parse_confirm(&self, answer: &str, default: Option<bool>) -> Result<bool, String> {
+            match (self.confirm_words.parse(answer), default) {
+                (Some(value), _) => Ok(value),
+                (None, Some(value)) if answer.trim().is_empty() => Ok(value),
+                (None, _) => Err(format!(
+                    "please answer {} or {}",
+                    self.confirm_words.yes[0], self.confirm_words.no[0]
+                )),
+            }
+        }
+
+        /// reads a secret without echoing it
+        ///
+        /// the line terminator is not a part of the secret
+        pub fn read_secret(&self) -> Result<Secret, InputReadError> {
+            Ok(Secret::new(self.reader.read_secret()?))
+        }
+
+        /// ask for a password
+        ///
+        /// if a confirmation question is given the password is asked
+        /// for the second time and both entries have to match,
+        /// a password from an answer source isn't confirmed
+        pub fn password<'p>(
+            &self,
+            prompt: impl Into<Prompt<'p>>,
+            confirmation: Option<&str>,
+        ) -> Result<Secret, InputReadError> {
+            let prompt = prompt.into();
+            if let Some((answer, _)) = self.source_answer(&prompt) {
+                return Ok(Secret::new(answer));
+            }
+            self.check_missing(&prompt)?;
+            self.writer.write_string(prompt.text)?;
+            let secret = self.read_secret()?;
+            if let Some(confirmation) = confirmation {
+                self.writer.write_string(confirmation)?;
+                if self.read_secret()? != secret {
+                    return Err(InputReadError::new(
+                        "wrong input. entries don't match".to_string(),
+                        ErrorKind::InputRequirementError,
+                    ));
+                }
+            }
+            Ok(secret)
+        }
+
         pub fn reader(&self) -> &T
         where
             T: Reader,
         {
             &self.reader
         }
+
+        pub fn writer(&self) -> &W {
+            &self.writer
+        }
+
+        /// like `demand` but returns the value produced by the claim
+        pub fn demand_value<F, Ft, Fe>(&self, claim: F) -> Result<Ft, InputReadError>
+        where
+            F: Fn(&str) -> Result<Ft, Fe>,
+            Fe: Display + Debug,
+        {
+            self.claim(None, &claim).map(|(_, value)| value)
+        }
+
+        /// like `demand_until` but returns the value produced by the claim
+        pub fn demand_value_until<F, Ft, Fe>(
+            &self,
+            claim: F,
+            attempts: Option<u8>,
+        ) -> Result<Ft, InputReadError>
+        where
+            F: Fn(&str) -> Result<Ft, Fe>,
+            Fe: Display + Debug,
+        {
+            self.claim_until(None, &claim, attempts)
+                .map(|(_, value)| value)
+        }
+
+        /// read and parse
+        ///
+        /// reads an input and parses it into `V`,
+        /// surrounding whitespace is ignored
+        pub fn demand_as<V>(&self) -> Result<V, InputReadError>
+        where
+            V: FromStr,
+            V::Err: Display + Debug,
+        {
+            self.demand_value(|s: &str| s.trim().parse::<V>())
+        }
+
+        /// like `demand_as` but gives several attempts to type a valid value
+        pub fn demand_as_until<V>(&self, attempts: Option<u8>) -> Result<V, InputReadError>
+        where
+            V: FromStr,
+            V::Err: Display + Debug,
+        {
+            self.demand_value_until(|s: &str| s.trim().parse::<V>(), attempts)
+        }
+
+        /// asks the question, if any, or just reads
+        ///
+        /// gives the answer and the name of the answer source
+        /// if it didn't come from the reader
+        fn fetch(
+            &self,
+            prompt: Option<&Prompt>,
+        ) -> Result<(String, Option<String>), InputReadError> {
+            let Some(prompt) = prompt else {
+                return Ok((self.read()?, None));
+            };
+            let with_default = |inp: String| match prompt.default {
+                Some(default) if inp.is_empty() => default.to_string(),
+                _ => inp,
+            };
+            if let Some((answer, name)) = self.source_answer(prompt) {
+                return Ok((with_default(self.normalization.apply(answer)), Some(name)));
+            }
+            self.check_missing(prompt)?;
+            self.writer.write_string(&prompt.render())?;
+            let mut prompt = prompt.clone();
+            prompt.timeout = prompt.timeout.or(self.timeout);
+            prompt.completer = prompt.completer.or(self.completer.as_deref());
+            match self.reader.read_prompted(&prompt, &self.writer) {
+                Ok(inp) => Ok((with_default(self.normalization.apply(inp)), None)),
+                Err(err) if err.kind() == io::ErrorKind::TimedOut && prompt.default.is_some() => {
+                    // the answer line was left unfinished
+                    self.writer.write_string("\n")?;
+                    let answer = with_default(String::new());
+                    self.reader.timed_out(&prompt, &answer)?;
+                    Ok((answer, None))
+                }
+                Err(err) => Err(err.into()),
+            }
+        }
+
+        /// the answer of the first answer source knowing the prompt's id
+        /// and the name of the source
+        pub(crate) fn source_answer(&self, prompt: &Prompt) -> Option<(String, String)> {
+            let id = prompt.id?;
+            self.sources
+                .iter()
+                .find_map(|source| source.answer(id).map(|answer| (answer, source.name(id))))
+        }
+
+        /// fails a prompt no answer source knows if the reader
+        /// mustn't be asked
+        fn check_missing(&self, prompt: &Prompt) -> Result<(), InputReadError> {
+            if self.missing_answer == MissingAnswer::Ask {
+                return Ok(());
+            }
+            let msg = match prompt.id {
+                Some(id) => format!("no answer for '{}'", id),
+                None => format!("no answer for '{}'", prompt.text.trim()),
+            };
+            Err(InputReadError::new(msg, ErrorKind::MissingAnswer))
+        }
+
+        fn claim<F, Ft, Fe>(
+            &self,
+            prompt: Option<&Prompt>,
+            claim: &F,
+        ) -> Result<(String, Ft), InputReadError>
+        where
+            F: Fn(&str) -> Result<Ft, Fe>,
+            Fe: Display + Debug,
+        {
+            let (inp, source) = self.fetch(prompt)?;
+            match claim(&inp) {
+                Ok(value) => {
+                    self.remember(prompt, &inp, &source);
+                    Ok((inp, value))
+                }
+                Err(err) => Err(wrong_input(err, source)),
+            }
+        }
+
+        fn claim_until<F, Ft, Fe>(
+            &self,
+            prompt: Option<&Prompt>,
+            claim: &F,
+            attempts: Option<u8>,
+        ) -> Result<(String, Ft), InputReadError>
+        where
+            F: Fn(&str) -> Result<Ft, Fe>,
+            Fe: Display + Debug,
+        {
+            let attempts = attempts.unwrap_or(3u8);
+            let mut errors = Vec::new();
+            for attempt in 1..=attempts {
+                let (input_str, source) = self.fetch(prompt)?;
+                match claim(&input_str) {
+                    Ok(value) => {
+                        self.remember(prompt, &input_str, &source);
+                        return Ok((input_str, value));
+                    }
+                    // an answer source gives the same answer again
+                    Err(err) if source.is_some() => return Err(wrong_input(err, source)),
+                    Err(err) => {
+                        let msg = err.to_string();
+                        if attempt < attempts {
+                            self.report(prompt.is_some(), &msg, attempts - attempt)?;
+                        }
+                        errors.push(msg);
+                    }
+                }
+            }
+            Err(InputReadError::attempts_exceeded(errors))
+        }
+
+        /// passes a typed answer to the reader's history
+        fn remember(&self, prompt: Option<&Prompt>, answer: &str, source: &Option<String>) {
+            match prompt {
+                Some(prompt) if source.is_none() && prompt.history => {
+                    self.reader.remember(prompt, answer)
+                }
+                _ => {}
+            }
+        }
+
+        fn report(&self, prompted: bool, msg: &str, attempts_left: u8) -> Result<(), io::Error> {
+            let feedback = match &self.feedback {
+                Some(feedback) => feedback,
+                None if prompted => &Feedback::Writer,
+                None => &Feedback::Silent,
+            };
+            match feedback {
+                Feedback::Writer => self
+                    .writer
+                    .write_string(&format!("{}. attempts left: {}\n", msg, attempts_left)),
+                Feedback::Silent => Ok(()),
+                Feedback::Callback(callback) => {
+                    callback(msg, attempts_left);
+                    Ok(())
+                }
+            }
+        }
     }
 
-    impl<R, F, Ft, Fe> Claimy<F, Ft, Fe> for Input<R>
+    impl<R, W, F, Ft, Fe> Claimy<F, Ft, Fe> for Input<R, W>
     where
         R: Reader,
+        W: Writer,
         F: Fn(&str) -> Result<Ft, Fe>,
         Fe: Display + Debug,
     {
         fn demand(&self, claim: F) -> Result<String, InputReadError> {
-            let inp = self.reader.read_string()?;
-            match claim(&inp) {
-                Ok(_) => Ok(inp),
-                Err(err) => Err(InputReadError {
-                    msg: format!("wrong input. {}", err),
-                    kind: ErrorKind::InputRequirementError,
-                }),
-            }
+            self.claim(None, &claim).map(|(inp, _)| inp)
         }
 
         /// read and check
@@ -116,34 +1161,153 @@ pub mod cli {
         /// until a predicate isn't positive
         ///
         fn demand_until(&self, claim: F, attempts: Option<u8>) -> Result<String, InputReadError> {
-            let attempts = if let Some(attempts) = attempts {
-                attempts
+            self.claim_until(None, &claim, attempts).map(|(inp, _)| inp)
+        }
+    }
+
+    pub(crate) fn wrong_input<E>(err: E, source: Option<String>) -> InputReadError
+    where
+        E: Display,
+    {
+        let msg = match source {
+            Some(source) => format!("wrong input from {}. {}", source, err),
+            None => format!("wrong input. {}", err),
+        };
+        InputReadError::new(msg, ErrorKind::InputRequirementError)
+    }
+
+    /// finds an option by its number or by a beginning of its label
+    ///
+    /// labels are compared ignoring case, an exact match wins
+    /// over other labels starting with the same text, a number
+    /// out of the list's range is looked up among labels too,
+    /// e.g. for labels that are numbers themselves
+    fn match_option(answer: &str, labels: &[String]) -> Result<usize, String> {
+        let answer = answer.trim();
+        if answer.is_empty() {
+            return Err("please choose an option".to_string());
+        }
+        let number = answer.parse::<usize>().ok();
+        if let Some(number) = number.filter(|n| (1..=labels.len()).contains(n)) {
+            return Ok(number - 1);
+        }
+        let answer = answer.to_lowercase();
+        let lowered: Vec<String> = labels.iter().map(|l| l.to_lowercase()).collect();
+        if let Some(index) = lowered.iter().position(|l| *l == answer) {
+            return Ok(index);
+        }
+        let found: Vec<usize> = (0..labels.len())
+            .filter(|&i| lowered[i].starts_with(&answer))
+            .collect();
+        match found[..] {
+            [index] => Ok(index),
+            [] if number.is_some() => {
+                Err(format!("please type a number from 1 to {}", labels.len()))
+            }
+            [] => Err(format!("no option matches '{}'", answer)),
+            _ => Err(format!(
+                "'{}' matches several options: {}",
+                answer,
+                found
+                    .iter()
+                    .map(|&i| labels[i].as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+
+    /// parses a list of option numbers into sorted zero based indexes
+    ///
+    /// with only exclusions given, they are taken out of all options
+    fn parse_selection(answer: &str, count: usize) -> Result<Vec<usize>, String> {
+        let parts: Vec<&str> = answer
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .collect();
+        match parts[..] {
+            [] => return Err("please choose options or type 'none'".to_string()),
+            [part] if part.eq_ignore_ascii_case("none") => return Ok(Vec::new()),
+            _ => {}
+        }
+        let mut chosen = vec![false; count];
+        let mut excluded = vec![false; count];
+        let mut included_any = false;
+        for part in parts {
+            if part.eq_ignore_ascii_case("all") {
+                chosen.iter_mut().for_each(|c| *c = true);
+                included_any = true;
+            } else if let Some(range) = part.strip_prefix('!') {
+                for i in parse_range(range, count)? {
+                    excluded[i] = true;
+                }
             } else {
-                3u8
-            };
-            let mut last_error_msg = "".to_string();
-            for attempt in 0..attempts {
-                let input_str = self.read()?;
-                let claim_res = claim(&input_str);
-                if claim_res.is_ok() {
-                    return Ok(input_str);
-                } else if attempt == attempts - 1 {
-                    last_error_msg = claim_res.err().unwrap().to_string();
+                for i in parse_range(part, count)? {
+                    chosen[i] = true;
                 }
+                included_any = true;
             }
-            Err(InputReadError {
-                msg: format!("attempts to read input were failed. {}", last_error_msg),
-                kind: ErrorKind::AttemptsExceedError,
-            })
         }
+        Ok((0..count)
+            .filter(|&i| (chosen[i] || !included_any) && !excluded[i])
+            .collect())
     }
 
+    /// parses `5` or `5-9` into zero based indexes
+    fn parse_range(range: &str, count: usize) -> Result<std::ops::RangeInclusive<usize>, String> {
+        let number = |s: &str| match s.trim().parse::<usize>() {
+            Ok(n) if (1..=count).contains(&n) => Ok(n - 1),
+            Ok(_) => Err(format!("please type numbers from 1 to {}", count)),
+            Err(_) => Err(format!("'{}' is not an option number", s)),
+        };
+        match range.split_once('-') {
+            Some((from, to)) => {
+                let (from, to) = (number(from)?, number(to)?);
+                if from > to {
+                    return Err(format!("'{}' is not a valid range", range));
+                }
+                Ok(from..=to)
+            }
+            None => number(range).map(|n| n..=n),
+        }
+    }
+
+    /// a type filled by answering questions one by one
+    ///
+    /// usually derived with `#[derive(Form)]` from the `derive` feature
+    pub trait Form: Sized {
+        fn fill<R, W>(input: &Input<R, W>) -> Result<Self, InputReadError>
+        where
+            R: Reader,
+            W: Writer;
+    }
+
+    #[cfg(feature = "derive")]
+    pub use jaw_derive::Form;
+
     impl Default for Input<Stdin> {
         fn default() -> Self {
-            Input { reader: stdin() }
+            Input::new(stdin())
         }
     }
 }
 
+pub mod answers;
+#[cfg(feature = "async")]
+pub mod async_cli;
+pub mod completion;
+pub mod editor;
+pub mod fuzzy;
+pub mod hints;
+pub mod history;
+pub mod transcript;
+
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod validators;
+pub mod wizard;
+
+mod term;
+
 #[cfg(test)]
 mod tests;
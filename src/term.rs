@@ -0,0 +1,348 @@
+//! terminal settings
+//!
+//! only unix terminals are handled, elsewhere the guards do nothing
+
+use std::io;
+use std::time::Duration;
+
+/// the error of a read that didn't finish in time
+pub(crate) fn timed_out(timeout: Duration) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::TimedOut,
+        format!("no input within {:?}", timeout),
+    )
+}
+
+#[cfg(unix)]
+mod imp {
+    use std::io;
+    use std::mem::MaybeUninit;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::{Duration, Instant};
+
+    use super::timed_out;
+
+    const STDIN: libc::c_int = libc::STDIN_FILENO;
+
+    pub fn is_tty() -> bool {
+        unsafe { libc::isatty(STDIN) == 1 }
+    }
+
+    static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+    extern "C" fn on_interrupt(_signal: libc::c_int) {
+        INTERRUPTED.store(true, Ordering::SeqCst);
+    }
+
+    /// catches ctrl-c while alive instead of letting it kill the process
+    ///
+    /// it replaces the SIGINT handler of the application, which doesn't
+    /// run meanwhile, an ignored SIGINT is left ignored, the previous
+    /// handler is restored on drop
+    pub struct InterruptGuard {
+        saved: Option<libc::sigaction>,
+    }
+
+    impl InterruptGuard {
+        pub fn new() -> Result<InterruptGuard, io::Error> {
+            INTERRUPTED.store(false, Ordering::SeqCst);
+            let mut current = MaybeUninit::<libc::sigaction>::uninit();
+            if unsafe { libc::sigaction(libc::SIGINT, std::ptr::null(), current.as_mut_ptr()) } != 0
+            {
+                return Err(io::Error::last_os_error());
+            }
+            let current = unsafe { current.assume_init() };
+            if current.sa_sigaction == libc::SIG_IGN {
+                return Ok(InterruptGuard { saved: None });
+            }
+            let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+            action.sa_sigaction = on_interrupt as extern "C" fn(libc::c_int) as libc::sighandler_t;
+            // no SA_RESTART, a blocked poll has to wake up
+            action.sa_flags = 0;
+            unsafe {
+                libc::sigemptyset(&mut action.sa_mask);
+                if libc::sigaction(libc::SIGINT, &action, std::ptr::null_mut()) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+            Ok(InterruptGuard {
+                saved: Some(current),
+            })
+        }
+    }
+
+    impl Drop for InterruptGuard {
+        fn drop(&mut self) {
+            if let Some(saved) = &self.saved {
+                unsafe {
+                    libc::sigaction(libc::SIGINT, saved, std::ptr::null_mut());
+                }
+            }
+        }
+    }
+
+    /// what the handler does on ctrl-c, without a signal
+    #[cfg(test)]
+    pub fn interrupt() {
+        INTERRUPTED.store(true, Ordering::SeqCst);
+    }
+
+    fn interrupted() -> io::Error {
+        io::Error::new(io::ErrorKind::Interrupted, "interrupted")
+    }
+
+    /// waits until stdin has something to read,
+    /// `false` if the timeout passed first
+    ///
+    /// ctrl-c caught by an `InterruptGuard` ends the wait with
+    /// `io::ErrorKind::Interrupted` unless interrupts are ignored
+    pub fn wait_readable(
+        timeout: Option<Duration>,
+        ignore_interrupts: bool,
+    ) -> Result<bool, io::Error> {
+        wait_fd(STDIN, timeout, ignore_interrupts)
+    }
+
+    fn wait_fd(
+        fd: libc::c_int,
+        timeout: Option<Duration>,
+        ignore_interrupts: bool,
+    ) -> Result<bool, io::Error> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        loop {
+            if INTERRUPTED.swap(false, Ordering::SeqCst) && !ignore_interrupts {
+                return Err(interrupted());
+            }
+            let millis = match deadline {
+                Some(deadline) => {
+                    let left = deadline.saturating_duration_since(Instant::now());
+                    left.as_millis().min(libc::c_int::MAX as u128) as libc::c_int
+                }
+                None => -1,
+            };
+            let mut poll_fd = libc::pollfd {
+                fd,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            match unsafe { libc::poll(&mut poll_fd, 1, millis) } {
+                -1 => {
+                    let err = io::Error::last_os_error();
+                    if err.kind() != io::ErrorKind::Interrupted {
+                        return Err(err);
+                    }
+                }
+                0 => return Ok(false),
+                _ => return Ok(true),
+            }
+        }
+    }
+
+    /// reads a line of stdin like `wait_readable` waits
+    pub fn read_line(
+        timeout: Option<Duration>,
+        ignore_interrupts: bool,
+    ) -> Result<String, io::Error> {
+        read_line_fd(STDIN, timeout, ignore_interrupts)
+    }
+
+    /// reads a line byte by byte, the timeout is for the whole line
+    ///
+    /// nothing is read past the line end, so the next line stays
+    /// in the descriptor where a poll sees it, a line buffered by
+    /// `Stdin` isn't in the descriptor any more and isn't seen
+    pub fn read_line_fd(
+        fd: libc::c_int,
+        timeout: Option<Duration>,
+        ignore_interrupts: bool,
+    ) -> Result<String, io::Error> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let mut line = Vec::new();
+        loop {
+            let left = deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+            if !wait_fd(fd, left, ignore_interrupts)? {
+                return Err(timed_out(timeout.unwrap_or_default()));
+            }
+            let mut byte = 0u8;
+            match unsafe { libc::read(fd, (&mut byte as *mut u8).cast(), 1) } {
+                -1 => {
+                    let err = io::Error::last_os_error();
+                    if err.kind() != io::ErrorKind::Interrupted {
+                        return Err(err);
+                    }
+                }
+                0 if line.is_empty() => {
+                    return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "end of input"));
+                }
+                0 => break,
+                _ => {
+                    line.push(byte);
+                    if byte == b'\n' {
+                        break;
+                    }
+                }
+            }
+        }
+        String::from_utf8(line).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stream did not contain valid UTF-8",
+            )
+        })
+    }
+
+    /// puts the terminal in raw mode while alive
+    ///
+    /// keys come byte by byte without echo, ctrl-c and ctrl-d
+    /// arrive as bytes rather than signals and line ends,
+    /// the saved settings are restored on drop
+    pub struct RawGuard {
+        saved: libc::termios,
+    }
+
+    impl RawGuard {
+        pub fn new() -> Result<RawGuard, io::Error> {
+            let mut termios = MaybeUninit::<libc::termios>::uninit();
+            if unsafe { libc::tcgetattr(STDIN, termios.as_mut_ptr()) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let saved = unsafe { termios.assume_init() };
+            let mut raw = saved;
+            raw.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG | libc::IEXTEN);
+            raw.c_iflag &= !(libc::IXON | libc::ICRNL | libc::INLCR);
+            raw.c_cc[libc::VMIN] = 1;
+            raw.c_cc[libc::VTIME] = 0;
+            if unsafe { libc::tcsetattr(STDIN, libc::TCSANOW, &raw) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(RawGuard { saved })
+        }
+    }
+
+    impl Drop for RawGuard {
+        fn drop(&mut self) {
+            unsafe {
+                libc::tcsetattr(STDIN, libc::TCSANOW, &self.saved);
+            }
+        }
+    }
+
+    /// unbuffered bytes of stdin
+    pub struct StdinBytes;
+
+    impl io::Read for StdinBytes {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = unsafe { libc::read(STDIN, buf.as_mut_ptr().cast(), buf.len()) };
+            if n < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(n as usize)
+        }
+    }
+
+    /// keeps terminal echo off while alive
+    ///
+    /// the saved settings are restored on drop, which also happens
+    /// while unwinding a panic
+    pub struct EchoGuard {
+        saved: Option<libc::termios>,
+    }
+
+    impl EchoGuard {
+        pub fn new() -> Result<EchoGuard, io::Error> {
+            if !is_tty() {
+                return Ok(EchoGuard { saved: None });
+            }
+            let mut termios = MaybeUninit::<libc::termios>::uninit();
+            if unsafe { libc::tcgetattr(STDIN, termios.as_mut_ptr()) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let saved = unsafe { termios.assume_init() };
+            let mut hidden = saved;
+            hidden.c_lflag &= !libc::ECHO;
+            hidden.c_lflag |= libc::ECHONL;
+            if unsafe { libc::tcsetattr(STDIN, libc::TCSANOW, &hidden) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(EchoGuard { saved: Some(saved) })
+        }
+    }
+
+    impl Drop for EchoGuard {
+        fn drop(&mut self) {
+            if let Some(saved) = &self.saved {
+                unsafe {
+                    libc::tcsetattr(STDIN, libc::TCSANOW, saved);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod imp {
+    use std::io;
+    use std::time::Duration;
+
+    /// polling isn't supported, a read just blocks
+    pub fn wait_readable(
+        _timeout: Option<Duration>,
+        _ignore_interrupts: bool,
+    ) -> Result<bool, io::Error> {
+        Ok(true)
+    }
+
+    /// reads a line of stdin ignoring the timeout
+    pub fn read_line(
+        _timeout: Option<Duration>,
+        _ignore_interrupts: bool,
+    ) -> Result<String, io::Error> {
+        let mut buf = String::new();
+        if io::stdin().read_line(&mut buf)? == 0 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "end of input"));
+        }
+        Ok(buf)
+    }
+
+    pub struct InterruptGuard;
+
+    impl InterruptGuard {
+        pub fn new() -> Result<InterruptGuard, io::Error> {
+            Ok(InterruptGuard)
+        }
+    }
+
+    pub fn is_tty() -> bool {
+        false
+    }
+
+    pub struct EchoGuard;
+
+    impl EchoGuard {
+        pub fn new() -> Result<EchoGuard, io::Error> {
+            Ok(EchoGuard)
+        }
+    }
+
+    pub struct RawGuard;
+
+    impl RawGuard {
+        pub fn new() -> Result<RawGuard, io::Error> {
+            Err(io::ErrorKind::Unsupported.into())
+        }
+    }
+
+    pub struct StdinBytes;
+
+    impl io::Read for StdinBytes {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::Unsupported.into())
+        }
+    }
+}
+
+#[cfg(all(unix, test))]
+pub(crate) use imp::{interrupt, read_line_fd};
+pub(crate) use imp::{
+    is_tty, read_line, wait_readable, EchoGuard, InterruptGuard, RawGuard, StdinBytes,
+};
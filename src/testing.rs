@@ -0,0 +1,155 @@
+//! # Testing
+//!
+//! a scripted user to test cli flows built on `Input`,
+//! available with the `testing` feature
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io;
+use std::rc::Rc;
+
+use crate::cli::{Input, Prompt, Reader, Writer};
+
+#[derive(Default)]
+struct Script {
+    answers: VecDeque<String>,
+    reads: usize,
+    output: String,
+    /// output written since the last read
+    pending: String,
+    prompts: Vec<String>,
+    unanswered: Option<String>,
+}
+
+impl Script {
+    /// the last line written before a read is taken as its prompt
+    fn take_prompt(&mut self) -> String {
+        let prompt = match self.pending.rsplit_once('\n') {
+            Some((_, last)) => last.to_string(),
+            None => self.pending.clone(),
+        };
+        self.pending.clear();
+        prompt
+    }
+
+    fn answer(&mut self, prompt: String) -> Result<String, io::Error> {
+        self.reads += 1;
+        self.prompts.push(prompt.clone());
+        match self.answers.pop_front() {
+            Some(answer) => Ok(answer),
+            None => {
+                let msg = format!("no scripted answer for prompt '{}'", prompt);
+                self.unanswered = Some(prompt);
+                Err(io::Error::new(io::ErrorKind::UnexpectedEof, msg))
+            }
+        }
+    }
+}
+
+/// a queue of answers given in order
+///
+/// it's a `Writer` as well, clones share the script so one can read
+/// and another one write, which is what `input` sets up:
+///
+/// ```
+/// # use jaw::testing::ScriptedReader;
+/// let script = ScriptedReader::new(["8080"]);
+/// let input = script.input();
+/// let port: u16 = input.demand_as().unwrap();
+/// assert_eq!(port, 8080);
+/// script.assert_consumed();
+/// ```
+#[derive(Clone, Default)]
+pub struct ScriptedReader {
+    script: Rc<RefCell<Script>>,
+}
+
+impl ScriptedReader {
+    pub fn new<I, S>(answers: I) -> ScriptedReader
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let reader = ScriptedReader::default();
+        for answer in answers {
+            reader.push(answer);
+        }
+        reader
+    }
+
+    /// adds an answer to the end of the queue
+    pub fn push<S>(&self, answer: S)
+    where
+        S: Into<String>,
+    {
+        self.script.borrow_mut().answers.push_back(answer.into());
+    }
+
+    /// an input reading from and writing to the script
+    pub fn input(&self) -> Input<ScriptedReader, ScriptedReader> {
+        Input::new(self.clone()).with_writer(self.clone())
+    }
+
+    /// how many times an answer was asked for
+    pub fn reads(&self) -> usize {
+        self.script.borrow().reads
+    }
+
+    /// answers not given yet
+    pub fn remaining(&self) -> Vec<String> {
+        self.script.borrow().answers.iter().cloned().collect()
+    }
+
+    /// everything written so far
+    pub fn output(&self) -> String {
+        self.script.borrow().output.clone()
+    }
+
+    /// prompts in order they were answered
+    pub fn prompts(&self) -> Vec<String> {
+        self.script.borrow().prompts.clone()
+    }
+
+    /// a prompt asked after the answers ran out
+    pub fn unanswered(&self) -> Option<String> {
+        self.script.borrow().unanswered.clone()
+    }
+
+    /// # Panics
+    ///
+    /// if some answers weren't given or a prompt was left unanswered
+    pub fn assert_consumed(&self) {
+        if let Some(prompt) = self.unanswered() {
+            panic!("prompt '{}' wasn't answered", prompt);
+        }
+        let remaining = self.remaining();
+        assert!(
+            remaining.is_empty(),
+            "answers weren't consumed: {:?}",
+            remaining
+        );
+    }
+}
+
+impl Reader for ScriptedReader {
+    fn read_string(&self) -> Result<String, io::Error> {
+        let mut script = self.script.borrow_mut();
+        let prompt = script.take_prompt();
+        script.answer(prompt)
+    }
+
+    fn read_prompted(&self, prompt: &Prompt, _out: &dyn Writer) -> Result<String, io::Error> {
+        let mut script = self.script.borrow_mut();
+        script.take_prompt();
+        script.answer(prompt.render())
+    }
+}
+
+impl Writer for ScriptedReader {
+    fn write_string(&self, s: &str) -> Result<(), io::Error> {
+        let mut script = self.script.borrow_mut();
+        script.output.push_str(s);
+        script.pending.push_str(s);
+        Ok(())
+    }
+}
@@ -1,14 +1,56 @@
-use crate::cli::{Claimy, ErrorKind, Input, Reader};
-use mocki::{Mock, Mocki};
+// the baseline tests are kept as they were written
+#![allow(clippy::bool_assert_comparison, clippy::needless_return)]
 
-impl Reader for Mock<String> {
+use crate::cli::{
+    Claimy, ConfirmWords, ErrorKind, Feedback, Input, Normalization, Prompt, Reader, Writer,
+};
+use crate::testing::ScriptedReader;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+#[derive(Default)]
+struct Output {
+    buf: RefCell<String>,
+}
+
+impl Writer for Output {
+    fn write_string(&self, s: &str) -> Result<(), std::io::Error> {
+        self.buf.borrow_mut().push_str(s);
+        Ok(())
+    }
+}
+
+impl Output {
+    fn text(&self) -> String {
+        self.buf.borrow().clone()
+    }
+}
+
+/// an input that's already closed
+struct Closed;
+
+impl Reader for Closed {
     fn read_string(&self) -> Result<String, std::io::Error> {
-        Ok(self.mock_once())
+        Err(std::io::ErrorKind::UnexpectedEof.into())
     }
 }
 
-fn create_stdin_mock() -> Mock<String> {
-    Mock::new()
+/// an input nobody types into
+struct Idle;
+
+impl Reader for Idle {
+    fn read_string(&self) -> Result<String, std::io::Error> {
+        unreachable!("a read without timeout would block forever")
+    }
+
+    fn read_string_timeout(&self, _timeout: Duration) -> Result<String, std::io::Error> {
+        Err(std::io::ErrorKind::TimedOut.into())
+    }
+}
+
+fn create_stdin_mock() -> ScriptedReader {
+    ScriptedReader::default()
 }
 
 #[test]
@@ -20,8 +62,8 @@ fn basic() {
 #[test]
 fn should_get_bool_input() {
     let stdin_mock = create_stdin_mock();
-    stdin_mock.add_value("true".into());
-    stdin_mock.add_value("false".into());
+    stdin_mock.push("true");
+    stdin_mock.push("false");
     let input = Input::new(stdin_mock);
     let input_result = input.read();
     assert!(input_result.is_ok());
@@ -30,8 +72,8 @@ fn should_get_bool_input() {
 
 #[test]
 fn should_fail_on_wrong_input() {
-    let stdin_mock = Mock::new();
-    stdin_mock.add_value("no, i don't have to type what you want!".to_string());
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("no, i don't have to type what you want!".to_string());
     let input = Input::new(stdin_mock);
     let input_result = input.demand(|s| {
         return s.parse::<u8>();
@@ -45,8 +87,8 @@ fn should_fail_on_wrong_input() {
 
 #[test]
 fn should_satisfy_input_demand() {
-    let stdin_mock = Mock::new();
-    stdin_mock.add_value("10".to_string());
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("10".to_string());
     let input = Input::new(stdin_mock);
     let input_result = input.demand(|s| {
         return s.parse::<u8>();
@@ -57,9 +99,9 @@ fn should_satisfy_input_demand() {
 #[test]
 fn should_succeed_on_third_attempt() {
     let stdin_mock = create_stdin_mock();
-    stdin_mock.add_value("what?".into());
-    stdin_mock.add_value("1".into());
-    stdin_mock.add_value("100".into());
+    stdin_mock.push("what?");
+    stdin_mock.push("1");
+    stdin_mock.push("100");
     let input = Input::new(stdin_mock);
     let input_res = input.demand_until(
         |s| match s.parse::<u16>() {
@@ -72,5 +114,1419 @@ fn should_succeed_on_third_attempt() {
         Some(3),
     );
     assert!(input_res.is_ok());
-    assert!(input.reader().calls() == 3);
+    assert!(input.reader().reads() == 3);
+}
+
+#[test]
+fn should_return_claimed_value() {
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("42");
+    let input = Input::new(stdin_mock);
+    let value = input.demand_value(|s| s.parse::<u8>());
+    assert_eq!(value.unwrap(), 42);
+}
+
+#[test]
+fn should_parse_input_as_type() {
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("8080\n");
+    let input = Input::new(stdin_mock);
+    let port: u16 = input.demand_as().unwrap();
+    assert_eq!(port, 8080);
+}
+
+#[test]
+fn should_keep_claim_error_in_typed_demand() {
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("abc");
+    stdin_mock.push("-1");
+    let input = Input::new(stdin_mock);
+    let res = input.demand_as_until::<u8>(Some(2));
+    let err = res.err().unwrap();
+    assert_eq!(&ErrorKind::AttemptsExceedError, err.kind());
+    assert!(err.to_string().contains("invalid digit"));
+}
+
+#[test]
+fn should_write_prompt_before_reading() {
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("Alice");
+    let input = Input::new(stdin_mock).with_writer(Output::default());
+    let answer = input.prompt("Name: ").unwrap();
+    assert_eq!(answer, "Alice");
+    assert_eq!(input.writer().text(), "Name: ");
+}
+
+#[test]
+fn should_report_rejected_attempts() {
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("x");
+    stdin_mock.push("7");
+    let input = Input::new(stdin_mock)
+        .with_writer(Output::default())
+        .with_feedback(Feedback::Writer);
+    let res = input.demand_value_until(|s| s.parse::<u8>(), Some(3));
+    assert_eq!(res.unwrap(), 7);
+    assert_eq!(
+        input.writer().text(),
+        "invalid digit found in string. attempts left: 2\n"
+    );
+}
+
+#[test]
+fn should_report_only_prompted_attempts_by_default() {
+    let stdin_mock = create_stdin_mock();
+    for answer in ["x", "7", "x", "7"] {
+        stdin_mock.push(answer);
+    }
+    let input = Input::new(stdin_mock).with_writer(Output::default());
+    assert_eq!(input.demand_as_until::<u8>(None).unwrap(), 7);
+    assert_eq!(input.writer().text(), "");
+    assert_eq!(input.ask("N: ", |s| s.parse::<u8>(), None).unwrap(), 7);
+    assert_eq!(
+        input.writer().text(),
+        "N: invalid digit found in string. attempts left: 2\nN: "
+    );
+}
+
+#[test]
+fn should_collect_every_attempt_error() {
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("a");
+    stdin_mock.push("");
+    let reported = Rc::new(RefCell::new(Vec::new()));
+    let sink = reported.clone();
+    let input =
+        Input::new(stdin_mock).with_feedback(Feedback::Callback(Box::new(move |msg, left| {
+            sink.borrow_mut().push((msg.to_string(), left))
+        })));
+    let err = input
+        .demand_until(|s| s.parse::<u8>(), Some(2))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.attempt_errors(),
+        [
+            "invalid digit found in string",
+            "cannot parse integer from empty string"
+        ]
+    );
+    assert_eq!(
+        *reported.borrow(),
+        [("invalid digit found in string".to_string(), 1)]
+    );
+}
+
+#[test]
+fn should_confirm_with_default() {
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("\n");
+    stdin_mock.push("YES\n");
+    let input = Input::new(stdin_mock).with_writer(Output::default());
+    assert!(!input.confirm("Continue?", Some(false)).unwrap());
+    assert!(input.confirm("Continue?", Some(false)).unwrap());
+    assert_eq!(input.writer().text(), "Continue? [y/N] Continue? [y/N] ");
+}
+
+#[test]
+fn should_fail_confirm_on_unknown_answer() {
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("maybe");
+    stdin_mock.push("");
+    let input = Input::new(stdin_mock).with_writer(Output::default());
+    let err = input.confirm("Continue?", None).err().unwrap();
+    assert_eq!(&ErrorKind::InputRequirementError, err.kind());
+    let err = input
+        .confirm_until("Continue?", None, Some(1))
+        .err()
+        .unwrap();
+    assert_eq!(&ErrorKind::AttemptsExceedError, err.kind());
+}
+
+#[test]
+fn should_confirm_with_custom_words() {
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("nein");
+    stdin_mock.push("Ja");
+    let input = Input::new(stdin_mock)
+        .with_writer(Output::default())
+        .with_confirm_words(ConfirmWords::new(&["j", "ja"], &["n", "nein"]));
+    assert!(!input.confirm_until("Weiter?", Some(true), None).unwrap());
+    assert!(input.confirm_until("Weiter?", Some(true), None).unwrap());
+    assert!(input.writer().text().starts_with("Weiter? [J/n] "));
+}
+
+#[test]
+fn should_select_by_number_or_prefix() {
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("2\n");
+    stdin_mock.push("ba\n");
+    let input = Input::new(stdin_mock).with_writer(Output::default());
+    let items = ["apple", "banana", "cherry"];
+    assert_eq!(*input.select("Fruit: ", &items, None).unwrap(), "banana");
+    assert_eq!(input.select_index("Fruit: ", &items, None).unwrap(), 1);
+    assert!(input
+        .writer()
+        .text()
+        .starts_with("1) apple\n2) banana\n3) cherry\nFruit: "));
+}
+
+#[test]
+fn should_reject_ambiguous_selection() {
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("ch");
+    stdin_mock.push("4");
+    let input = Input::new(stdin_mock).with_writer(Output::default());
+    let items = ["cherry", "chestnut", "apple"];
+    let err = input.select("Pick: ", &items, Some(2)).err().unwrap();
+    assert_eq!(&ErrorKind::AttemptsExceedError, err.kind());
+    assert_eq!(
+        err.attempt_errors(),
+        [
+            "'ch' matches several options: cherry, chestnut",
+            "please type a number from 1 to 3"
+        ]
+    );
+}
+
+#[test]
+fn should_select_numeric_labels() {
+    let stdin_mock = create_stdin_mock();
+    for answer in ["10", "2", "300", "25"] {
+        stdin_mock.push(answer);
+    }
+    let input = Input::new(stdin_mock).with_writer(Output::default());
+    let ports = [10, 20, 300];
+    assert_eq!(*input.select("Port: ", &ports, None).unwrap(), 10);
+    assert_eq!(*input.select("Port: ", &ports, None).unwrap(), 20);
+    assert_eq!(*input.select("Port: ", &ports, None).unwrap(), 300);
+    let err = input.select("Port: ", &ports, Some(1)).err().unwrap();
+    assert_eq!(err.attempt_errors(), ["please type a number from 1 to 3"]);
+}
+
+#[test]
+fn should_multi_select_ranges_and_exclusions() {
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("1,3-5,!4\n");
+    stdin_mock.push("!2 !3");
+    stdin_mock.push("none");
+    let input = Input::new(stdin_mock).with_writer(Output::default());
+    let hosts = ["a", "b", "c", "d", "e"];
+    let chosen = input.multi_select("Hosts: ", &hosts).unwrap();
+    assert_eq!(chosen, [&"a", &"c", &"e"]);
+    let chosen = input.multi_select("Hosts: ", &hosts).unwrap();
+    assert_eq!(chosen, [&"a", &"d", &"e"]);
+    assert!(input.multi_select("Hosts: ", &hosts).unwrap().is_empty());
+}
+
+#[test]
+fn should_fail_multi_select_on_wrong_list() {
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("2-7");
+    let input = Input::new(stdin_mock).with_writer(Output::default());
+    let err = input.multi_select("Hosts: ", &["a", "b"]).err().unwrap();
+    assert_eq!(&ErrorKind::InputRequirementError, err.kind());
+    assert_eq!(
+        err.to_string(),
+        "wrong input. please type numbers from 1 to 2"
+    );
+}
+
+#[test]
+fn should_read_confirmed_password() {
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("s3cret\n");
+    stdin_mock.push("s3cret\n");
+    let input = Input::new(stdin_mock).with_writer(Output::default());
+    let secret = input
+        .password("Password: ", Some("Repeat password: "))
+        .unwrap();
+    assert_eq!(secret.expose(), "s3cret");
+    assert_eq!(format!("{:?}", secret), "Secret(***)");
+    assert_eq!(input.writer().text(), "Password: Repeat password: ");
+}
+
+#[test]
+fn should_fail_on_mismatched_password() {
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("one");
+    stdin_mock.push("two");
+    let input = Input::new(stdin_mock).with_writer(Output::default());
+    let err = input
+        .password("Password: ", Some("Repeat password: "))
+        .err()
+        .unwrap();
+    assert_eq!(&ErrorKind::InputRequirementError, err.kind());
+}
+
+#[test]
+fn should_strip_line_end_by_default() {
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("100\r\n");
+    stdin_mock.push(" 100 \n");
+    let input = Input::new(stdin_mock);
+    assert_eq!(input.demand_value(|s| s.parse::<u16>()).unwrap(), 100);
+    assert_eq!(input.read().unwrap(), " 100 ");
+}
+
+#[test]
+fn should_apply_configured_normalization() {
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push(" 100 \n");
+    stdin_mock.push("raw\n");
+    let input =
+        Input::new(stdin_mock).with_normalization(Normalization::default().trim_whitespace(true));
+    assert_eq!(input.read().unwrap(), "100");
+    let input = input.with_normalization(Normalization::none());
+    assert_eq!(input.read().unwrap(), "raw\n");
+}
+
+#[cfg(feature = "unicode")]
+#[test]
+fn should_normalize_to_nfc() {
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("e\u{301}\n");
+    let input = Input::new(stdin_mock).with_normalization(Normalization::default().nfc(true));
+    assert_eq!(input.read().unwrap(), "\u{e9}");
+}
+
+#[test]
+fn should_distinguish_end_of_input() {
+    let input = Input::new(Closed);
+    assert_eq!(input.read_opt().unwrap(), None);
+    let err = input.read().err().unwrap();
+    assert_eq!(&ErrorKind::EndOfInput, err.kind());
+}
+
+#[test]
+fn should_stop_demanding_on_end_of_input() {
+    let calls = std::cell::Cell::new(0);
+    struct Counted<'a>(&'a std::cell::Cell<u8>);
+    impl Reader for Counted<'_> {
+        fn read_string(&self) -> Result<String, std::io::Error> {
+            self.0.set(self.0.get() + 1);
+            Closed.read_string()
+        }
+    }
+    let input = Input::new(Counted(&calls));
+    let err = input
+        .demand_until(|s| s.parse::<u8>(), Some(3))
+        .err()
+        .unwrap();
+    assert_eq!(&ErrorKind::EndOfInput, err.kind());
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn should_use_default_on_empty_input() {
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("\n");
+    stdin_mock.push("9090\n");
+    let input = Input::new(stdin_mock).with_writer(Output::default());
+    let port = Prompt::new("Port: ").default("8080");
+    assert_eq!(input.prompt(port.clone()).unwrap(), "8080");
+    assert_eq!(input.ask(port, |s| s.parse::<u16>(), None).unwrap(), 9090);
+    assert_eq!(input.writer().text(), "Port [8080]: Port [8080]: ");
+}
+
+#[test]
+fn should_pass_default_through_claim() {
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("");
+    let input = Input::new(stdin_mock).with_writer(Output::default());
+    let err = input
+        .ask(
+            Prompt::new("Port").default("http"),
+            |s| s.parse::<u16>(),
+            Some(1),
+        )
+        .err()
+        .unwrap();
+    assert_eq!(&ErrorKind::AttemptsExceedError, err.kind());
+    assert_eq!(input.writer().text(), "Port [http] ");
+}
+
+#[test]
+fn should_combine_validators() {
+    use crate::validators::{and, map_err, max_len, min_len, non_empty, not, one_of, or};
+    let name = and(non_empty(), and(min_len(2), max_len(4)));
+    assert_eq!(name(" ").unwrap_err(), "can't be empty");
+    assert_eq!(
+        name("a").unwrap_err(),
+        "should be at least 2 characters long"
+    );
+    assert_eq!(
+        name("abcde").unwrap_err(),
+        "should be at most 4 characters long"
+    );
+    assert!(name("bob").is_ok());
+    let level = or(
+        one_of(&["low", "high"]),
+        map_err(one_of(&["max"]), |_| "should be max"),
+    );
+    assert_eq!(level("max").unwrap(), "max");
+    assert_eq!(
+        level("mid").unwrap_err(),
+        "should be one of: low, high or should be max"
+    );
+    let not_root = not(one_of(&["root"]), "root isn't allowed");
+    assert_eq!(not_root("root").unwrap_err(), "root isn't allowed");
+    assert!(not_root("admin").is_ok());
+}
+
+#[test]
+fn should_report_validator_message_in_demand() {
+    use crate::validators::in_range;
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("70000\n");
+    let input = Input::new(stdin_mock);
+    let err = input.demand_value(in_range(1..=1000u32)).err().unwrap();
+    assert_eq!(&ErrorKind::InputRequirementError, err.kind());
+    assert_eq!(err.to_string(), "wrong input. should be from 1 to 1000");
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn should_match_regex() {
+    let hex = crate::validators::matches_regex("^[0-9a-f]+$");
+    assert!(hex("c0ffee").is_ok());
+    assert_eq!(hex("tea").unwrap_err(), "should match ^[0-9a-f]+$");
+}
+
+#[test]
+fn should_go_back_and_edit_in_wizard() {
+    use crate::validators::non_empty;
+    use crate::wizard::Wizard;
+    let stdin_mock = create_stdin_mock();
+    for answer in ["alice", ":back", "bob", "30", "2", "", ""] {
+        stdin_mock.push(answer);
+    }
+    let input = Input::new(stdin_mock).with_writer(Output::default());
+    let wizard = Wizard::new()
+        .step("Name: ", non_empty())
+        .step("Age: ", |s| s.parse::<u8>());
+    let answers = wizard.run(&input).unwrap();
+    assert_eq!(answers, ["bob", "30"]);
+    let text = input.writer().text();
+    assert!(text.contains("Name [alice]: "));
+    assert!(text.contains("1) Name: bob\n2) Age: 30\n"));
+    assert!(text.contains("Age [30]: "));
+}
+
+#[test]
+fn should_clear_answer_in_wizard() {
+    use crate::wizard::Wizard;
+    let stdin_mock = create_stdin_mock();
+    for answer in ["dev", ":back", ":clear", "x", "2", ":clear", ""] {
+        stdin_mock.push(answer);
+    }
+    let input = Input::new(stdin_mock).with_writer(Output::default());
+    let answers = Wizard::new()
+        .step("Tag: ", |s| s.parse::<String>())
+        .step("Note: ", |s| s.parse::<String>())
+        .run(&input)
+        .unwrap();
+    assert_eq!(answers, ["", ""]);
+    input.reader().assert_consumed();
+    assert!(input.writer().text().contains("Note [x]: "));
+}
+
+#[test]
+fn should_keep_later_answers_when_going_back() {
+    use crate::wizard::Wizard;
+    let stdin_mock = create_stdin_mock();
+    for answer in ["a", "b", ":back", ":back", "", "", "c", ""] {
+        stdin_mock.push(answer);
+    }
+    let input = Input::new(stdin_mock).with_writer(Output::default());
+    let answers = Wizard::new()
+        .step("One: ", |s| s.parse::<String>())
+        .step("Two: ", |s| s.parse::<String>())
+        .step("Three: ", |s| s.parse::<String>())
+        .run(&input)
+        .unwrap();
+    assert_eq!(answers, ["a", "b", "c"]);
+    input.reader().assert_consumed();
+    assert!(input.writer().text().contains("Two [b]: "));
+}
+
+#[test]
+fn should_reject_going_back_in_empty_wizard() {
+    use crate::wizard::Wizard;
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push(":back");
+    stdin_mock.push("");
+    let input = Input::new(stdin_mock).with_writer(Output::default());
+    let answers = Wizard::new().run(&input).unwrap();
+    assert!(answers.is_empty());
+    assert!(input
+        .writer()
+        .text()
+        .contains("there are no answers to go back to"));
+}
+
+#[test]
+fn should_cancel_wizard() {
+    use crate::wizard::Wizard;
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push(":cancel");
+    let input = Input::new(stdin_mock).with_writer(Output::default());
+    let err = Wizard::new()
+        .step("Name: ", |s| s.parse::<String>())
+        .run(&input)
+        .err()
+        .unwrap();
+    assert_eq!(&ErrorKind::Cancelled, err.kind());
+}
+
+#[test]
+fn should_answer_from_environment() {
+    std::env::set_var("JAW_TEST_PORT", "9000");
+    std::env::set_var("JAW_TEST_LOG_LEVEL", "loud");
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("Bob");
+    let input = Input::new(stdin_mock)
+        .with_writer(Output::default())
+        .with_env_prefix("JAW_TEST");
+    let port = Prompt::new("Port: ").id("port");
+    assert_eq!(input.ask(port, |s| s.parse::<u16>(), None).unwrap(), 9000);
+    let name = Prompt::new("Name: ").id("name");
+    assert_eq!(input.prompt(name).unwrap(), "Bob");
+    assert_eq!(input.writer().text(), "Name: ");
+    let level = Prompt::new("Level: ").id("log-level");
+    let err = input.ask(level, |s| s.parse::<u8>(), None).err().unwrap();
+    assert_eq!(&ErrorKind::InputRequirementError, err.kind());
+    assert_eq!(
+        err.to_string(),
+        "wrong input from environment variable JAW_TEST_LOG_LEVEL. invalid digit found in string"
+    );
+}
+
+#[test]
+fn should_answer_choices_from_environment() {
+    std::env::set_var("JAW_CHOICE_DEPLOY", "yes");
+    std::env::set_var("JAW_CHOICE_FRUIT", "2");
+    std::env::set_var("JAW_CHOICE_HOSTS", "1,3");
+    std::env::set_var("JAW_CHOICE_TOKEN", "s3cret");
+    let input = Input::new(create_stdin_mock())
+        .with_writer(Output::default())
+        .with_env_prefix("JAW_CHOICE");
+    let deploy = Prompt::new("Deploy?").id("deploy");
+    assert!(input.confirm(deploy, Some(false)).unwrap());
+    let fruit = Prompt::new("Fruit: ").id("fruit");
+    assert_eq!(
+        *input.select(fruit, &["apple", "pear"], None).unwrap(),
+        "pear"
+    );
+    let hosts = Prompt::new("Hosts: ").id("hosts");
+    let chosen = input.multi_select(hosts, &["a", "b", "c"]).unwrap();
+    assert_eq!(chosen, [&"a", &"c"]);
+    let token = Prompt::new("Token: ").id("token");
+    let secret = input.password(token, Some("Repeat token: ")).unwrap();
+    assert_eq!(secret.expose(), "s3cret");
+    assert_eq!(input.writer().text(), "");
+}
+
+#[test]
+fn should_replay_answer_file() {
+    use crate::answers::{AnswerFile, MissingAnswer};
+    let path = std::env::temp_dir().join(format!("jaw-answers-{}.txt", std::process::id()));
+    std::fs::write(&path, "# recorded session\nhost = example.com\nport=8080\n").unwrap();
+    let answers = AnswerFile::open(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let input = Input::new(create_stdin_mock())
+        .with_writer(Output::default())
+        .with_source(answers)
+        .with_missing_answer(MissingAnswer::Fail);
+    let host = input.prompt(Prompt::new("Host: ").id("host")).unwrap();
+    assert_eq!(host, "example.com");
+    let port = input.ask(Prompt::new("Port: ").id("port"), |s| s.parse::<u16>(), None);
+    assert_eq!(port.unwrap(), 8080);
+    let err = input
+        .prompt(Prompt::new("User: ").id("user"))
+        .err()
+        .unwrap();
+    assert_eq!(&ErrorKind::MissingAnswer, err.kind());
+    assert_eq!(input.writer().text(), "");
+}
+
+#[test]
+fn should_run_wizard_from_answer_sources() {
+    use crate::answers::MissingAnswer;
+    use crate::wizard::Wizard;
+    std::env::set_var("JAW_WIZARD_NAME", "app");
+    std::env::set_var("JAW_WIZARD_REVIEW", "");
+    let input = Input::new(create_stdin_mock())
+        .with_writer(Output::default())
+        .with_env_prefix("JAW_WIZARD")
+        .with_missing_answer(MissingAnswer::Fail);
+    let answers = Wizard::new()
+        .step(Prompt::new("Name: ").id("name"), |s| s.parse::<String>())
+        .run(&input)
+        .unwrap();
+    assert_eq!(answers, ["app"]);
+    std::env::set_var("JAW_WIZARD_REVIEW", "1");
+    let err = Wizard::new()
+        .step(Prompt::new("Name: ").id("name"), |s| s.parse::<String>())
+        .run(&input)
+        .err()
+        .unwrap();
+    assert_eq!(&ErrorKind::InputRequirementError, err.kind());
+}
+
+#[test]
+fn should_fail_on_missing_password() {
+    use crate::answers::MissingAnswer;
+    let input = Input::new(create_stdin_mock())
+        .with_writer(Output::default())
+        .with_missing_answer(MissingAnswer::Fail);
+    let err = input
+        .password(Prompt::new("Password: ").id("password"), None)
+        .err()
+        .unwrap();
+    assert_eq!(&ErrorKind::MissingAnswer, err.kind());
+    assert_eq!(input.writer().text(), "");
+}
+
+#[test]
+fn should_reject_malformed_answer_lines() {
+    let err = crate::answers::parse_pairs("port 8080").err().unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[cfg(all(feature = "json", feature = "toml"))]
+#[test]
+fn should_read_structured_answer_files() {
+    use crate::answers::{AnswerFile, AnswerSource};
+    let dir = std::env::temp_dir();
+    let json = dir.join(format!("jaw-answers-{}.json", std::process::id()));
+    std::fs::write(&json, r#"{"server": {"port": 8080, "host": "a"}}"#).unwrap();
+    let toml = dir.join(format!("jaw-answers-{}.toml", std::process::id()));
+    std::fs::write(&toml, "[server]\nport = 9090\nhost = \"b\"\n").unwrap();
+    let (json_answers, toml_answers) = (
+        AnswerFile::open(&json).unwrap(),
+        AnswerFile::open(&toml).unwrap(),
+    );
+    std::fs::remove_file(&json).unwrap();
+    std::fs::remove_file(&toml).unwrap();
+    assert_eq!(json_answers.answer("server.port").unwrap(), "8080");
+    assert_eq!(json_answers.answer("server.host").unwrap(), "a");
+    assert_eq!(toml_answers.answer("server.port").unwrap(), "9090");
+    assert_eq!(toml_answers.answer("server.host").unwrap(), "b");
+}
+
+#[test]
+fn should_record_and_replay_session() {
+    use crate::transcript::{Recorder, Replay};
+    let path = std::env::temp_dir().join(format!("jaw-transcript-{}.txt", std::process::id()));
+    let stdin_mock = create_stdin_mock();
+    stdin_mock.push("a\tb\\c\n");
+    stdin_mock.push("42\n");
+    stdin_mock.push("hunter2\n");
+    let recorder = Recorder::create(stdin_mock, &path).unwrap();
+    let input = Input::new(recorder).with_writer(Output::default());
+    input.prompt(Prompt::new("Name: ").id("name")).unwrap();
+    input.read().unwrap();
+    input.read_secret().unwrap();
+    drop(input);
+
+    let replay = Replay::open(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(replay.remaining(), 3);
+    let input = Input::new(replay).with_writer(Output::default());
+    let name = input.prompt(Prompt::new("Name: ").id("name")).unwrap();
+    assert_eq!(name, "a\tb\\c");
+    assert_eq!(input.demand_as::<u8>().unwrap(), 42);
+    let err = input.read_secret().err().unwrap();
+    assert_eq!(&ErrorKind::IoError, err.kind());
+    let err = input.read().err().unwrap();
+    assert_eq!(&ErrorKind::EndOfInput, err.kind());
+}
+
+#[test]
+fn should_keep_replay_in_step_after_password() {
+    use crate::transcript::{Recorder, Replay};
+    let path = std::env::temp_dir().join(format!("jaw-secret-{}.txt", std::process::id()));
+    let stdin_mock = create_stdin_mock();
+    for answer in ["Bob\n", "hunter2\n", "8080\n"] {
+        stdin_mock.push(answer);
+    }
+    let recorder = Recorder::create(stdin_mock, &path).unwrap();
+    let input = Input::new(recorder).with_writer(Output::default());
+    input.prompt(Prompt::new("Name: ").id("name")).unwrap();
+    input.password("Password: ", None).unwrap();
+    input.prompt(Prompt::new("Port: ").id("port")).unwrap();
+    drop(input);
+    let transcript = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(!transcript.contains("hunter2"));
+
+    let replay = Replay::parse(&transcript).unwrap();
+    let input = Input::new(replay).with_writer(Output::default());
+    let name = input.prompt(Prompt::new("Name: ").id("name")).unwrap();
+    assert_eq!(name, "Bob");
+    let err = input.password("Password: ", None).err().unwrap();
+    assert_eq!(err.to_string(), "secrets aren't recorded in transcripts");
+    let port = input.prompt(Prompt::new("Port: ").id("port")).unwrap();
+    assert_eq!(port, "8080");
+}
+
+#[test]
+fn should_record_timed_and_picked_answers() {
+    use crate::transcript::{Recorder, Replay};
+    let input = Input::new(Recorder::new(Idle, std::io::sink()))
+        .with_writer(Output::default())
+        .with_timeout(Duration::from_millis(10));
+    let err = input.read().err().unwrap();
+    assert_eq!(&ErrorKind::Timeout, err.kind());
+
+    struct Picking;
+    impl Reader for Picking {
+        fn read_string(&self) -> Result<String, std::io::Error> {
+            unreachable!()
+        }
+        fn pick(
+            &self,
+            _: &Prompt,
+            _: &[String],
+            _: &dyn Writer,
+        ) -> Option<Result<usize, std::io::Error>> {
+            Some(Ok(1))
+        }
+    }
+    let path = std::env::temp_dir().join(format!("jaw-picked-{}.txt", std::process::id()));
+    let items = ["main", "develop"];
+    let input =
+        Input::new(Recorder::create(Picking, &path).unwrap()).with_writer(Output::default());
+    let branch = Prompt::new("Branch: ").id("branch");
+    assert_eq!(
+        input.fuzzy_select(branch.clone(), &items, None).unwrap(),
+        &"develop"
+    );
+    drop(input);
+    let replay = Replay::open(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let input = Input::new(replay).with_writer(Output::default());
+    assert_eq!(
+        input.fuzzy_select(branch, &items, None).unwrap(),
+        &"develop"
+    );
+}
+
+/// a pipe with the bytes written at once, read and write ends
+#[cfg(unix)]
+fn pipe_with(bytes: &[u8]) -> (libc::c_int, libc::c_int) {
+    let mut fds = [0; 2];
+    assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+    let written = unsafe { libc::write(fds[1], bytes.as_ptr().cast(), bytes.len()) };
+    assert_eq!(written, bytes.len() as isize);
+    (fds[0], fds[1])
+}
+
+/// held by tests reading with the interrupt flag, which is global
+#[cfg(unix)]
+static INTERRUPT_FLAG: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+#[test]
+fn should_replay_default_taken_on_timeout() {
+    use crate::transcript::{Recorder, Replay};
+    struct Late {
+        script: ScriptedReader,
+    }
+    impl Reader for Late {
+        fn read_string(&self) -> Result<String, std::io::Error> {
+            self.script.read_string()
+        }
+        fn read_string_timeout(&self, _: Duration) -> Result<String, std::io::Error> {
+            Err(std::io::ErrorKind::TimedOut.into())
+        }
+    }
+    let path = std::env::temp_dir().join(format!("jaw-timed-{}.txt", std::process::id()));
+    let late = Late {
+        script: ScriptedReader::new(["bob"]),
+    };
+    let input = Input::new(Recorder::create(late, &path).unwrap()).with_writer(Output::default());
+    let port = Prompt::new("Port: ")
+        .id("port")
+        .default("80")
+        .timeout(Duration::from_millis(10));
+    assert_eq!(input.prompt(port.clone()).unwrap(), "80");
+    let name = Prompt::new("Name: ").id("name");
+    assert_eq!(input.prompt(name.clone()).unwrap(), "bob");
+    drop(input);
+
+    let replay = Replay::open(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let input = Input::new(replay).with_writer(Output::default());
+    assert_eq!(input.prompt(port).unwrap(), "80");
+    assert_eq!(input.prompt(name).unwrap(), "bob");
+    assert_eq!(input.reader().remaining(), 0);
+}
+
+#[cfg(unix)]
+#[test]
+fn should_read_lines_written_at_once_within_timeout() {
+    use crate::term::read_line_fd;
+    let _flag = INTERRUPT_FLAG.lock().unwrap();
+    let (read_end, write_end) = pipe_with(b"a\nb\n");
+    let timeout = Some(Duration::from_millis(200));
+    assert_eq!(read_line_fd(read_end, timeout, false).unwrap(), "a\n");
+    assert_eq!(read_line_fd(read_end, timeout, false).unwrap(), "b\n");
+    let err = read_line_fd(read_end, timeout, false).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+    unsafe {
+        libc::close(read_end);
+        libc::close(write_end);
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn should_read_lines_written_at_once_without_timeout() {
+    use crate::term::read_line_fd;
+    let _flag = INTERRUPT_FLAG.lock().unwrap();
+    let (read_end, write_end) = pipe_with(b"a\nb");
+    assert_eq!(read_line_fd(read_end, None, false).unwrap(), "a\n");
+    unsafe { libc::close(write_end) };
+    assert_eq!(read_line_fd(read_end, None, false).unwrap(), "b");
+    let err = read_line_fd(read_end, None, false).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    unsafe { libc::close(read_end) };
+}
+
+#[test]
+fn should_refuse_replaying_answer_of_other_prompt() {
+    use crate::transcript::Replay;
+    let replay = Replay::parse("1700000000000\tport\t8080\\n\n").unwrap();
+    let input = Input::new(replay).with_writer(Output::default());
+    let err = input
+        .prompt(Prompt::new("Host: ").id("host"))
+        .err()
+        .unwrap();
+    assert_eq!(&ErrorKind::IoError, err.kind());
+    assert_eq!(
+        input.prompt(Prompt::new("Port: ").id("port")).unwrap(),
+        "8080"
+    );
+}
+
+#[test]
+fn should_report_unanswered_prompt() {
+    let script = ScriptedReader::new(["Bob\n"]);
+    let input = script.input();
+    assert_eq!(input.prompt("Name: ").unwrap(), "Bob");
+    let port = input.ask(
+        Prompt::new("Port: ").default("80"),
+        |s| s.parse::<u16>(),
+        None,
+    );
+    assert_eq!(&ErrorKind::EndOfInput, port.err().unwrap().kind());
+    assert_eq!(script.prompts(), ["Name: ", "Port [80]: "]);
+    assert_eq!(script.unanswered().unwrap(), "Port [80]: ");
+}
+
+#[test]
+#[should_panic(expected = "answers weren't consumed")]
+fn should_panic_on_unused_answers() {
+    let script = ScriptedReader::new(["yes", "no"]);
+    script.input().read().unwrap();
+    script.assert_consumed();
+}
+
+#[test]
+fn should_record_prompt_after_written_list() {
+    let script = ScriptedReader::new(["2"]);
+    let input = script.input();
+    assert_eq!(
+        *input.select("Fruit: ", &["apple", "pear"], None).unwrap(),
+        "pear"
+    );
+    assert_eq!(script.prompts(), ["Fruit: "]);
+    assert_eq!(script.output(), "1) apple\n2) pear\nFruit: ");
+    script.assert_consumed();
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn should_demand_asynchronously() {
+    use crate::async_cli::{AsyncInput, AsyncLines};
+    let input = AsyncInput::new(AsyncLines::new(&b"what?\n100\nx\n"[..]));
+    let value = input.demand_value_until(|s| s.parse::<u16>(), None).await;
+    assert_eq!(value.unwrap(), 100);
+    let err = input.demand(|s| s.parse::<u16>()).await.err().unwrap();
+    assert_eq!(&ErrorKind::InputRequirementError, err.kind());
+    let err = input.read().await.err().unwrap();
+    assert_eq!(&ErrorKind::EndOfInput, err.kind());
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn should_fail_async_demand_after_attempts() {
+    use crate::async_cli::{AsyncInput, AsyncLines};
+    let input = AsyncInput::new(AsyncLines::new(&b"a\nb\n"[..]));
+    let err = input
+        .demand_until(|s| s.parse::<u8>(), Some(2))
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(&ErrorKind::AttemptsExceedError, err.kind());
+    assert_eq!(err.attempt_errors().len(), 2);
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn should_report_rejected_async_attempts() {
+    use crate::async_cli::{AsyncInput, AsyncLines};
+    use std::sync::{Arc, Mutex};
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&reported);
+    let input = AsyncInput::new(AsyncLines::new(&b"a\nb\n7\n"[..]))
+        .with_feedback(move |msg, left| sink.lock().unwrap().push((msg.to_string(), left)));
+    let value = input.demand_value_until(|s| s.parse::<u8>(), None).await;
+    assert_eq!(value.unwrap(), 7);
+    assert_eq!(
+        *reported.lock().unwrap(),
+        [
+            ("invalid digit found in string".to_string(), 2),
+            ("invalid digit found in string".to_string(), 1)
+        ]
+    );
+}
+
+#[test]
+fn should_time_out_waiting_for_input() {
+    let input = Input::new(Idle)
+        .with_writer(Output::default())
+        .with_timeout(Duration::from_secs(1));
+    let err = input.read().err().unwrap();
+    assert_eq!(&ErrorKind::Timeout, err.kind());
+    let err = input
+        .demand_until(|s| s.parse::<u8>(), Some(3))
+        .err()
+        .unwrap();
+    assert_eq!(&ErrorKind::Timeout, err.kind());
+}
+
+#[test]
+fn should_use_default_on_timeout() {
+    let input = Input::new(Idle).with_writer(Output::default());
+    let prompt = Prompt::new("Port: ")
+        .default("8080")
+        .timeout(Duration::from_millis(10));
+    let port = input.ask(prompt, |s| s.parse::<u16>(), None).unwrap();
+    assert_eq!(port, 8080);
+    assert_eq!(input.writer().text(), "Port [8080]: \n");
+}
+
+#[test]
+fn should_report_interrupted_read() {
+    struct CtrlC;
+    impl Reader for CtrlC {
+        fn read_string(&self) -> Result<String, std::io::Error> {
+            Err(std::io::ErrorKind::Interrupted.into())
+        }
+    }
+    let input = Input::new(CtrlC).with_writer(Output::default());
+    let err = input.confirm_until("Continue?", None, None).err().unwrap();
+    assert_eq!(&ErrorKind::Interrupted, err.kind());
+}
+
+#[cfg(unix)]
+#[test]
+fn should_leave_ignored_ctrl_c_alone() {
+    use crate::term::InterruptGuard;
+    use std::mem::MaybeUninit;
+    let _flag = INTERRUPT_FLAG.lock().unwrap();
+    let disposition = || unsafe {
+        let mut action = MaybeUninit::<libc::sigaction>::uninit();
+        libc::sigaction(libc::SIGINT, std::ptr::null(), action.as_mut_ptr());
+        action.assume_init().sa_sigaction
+    };
+    let previous = unsafe { libc::signal(libc::SIGINT, libc::SIG_IGN) };
+    let guard = InterruptGuard::new().unwrap();
+    assert_eq!(disposition(), libc::SIG_IGN);
+    drop(guard);
+    assert_eq!(disposition(), libc::SIG_IGN);
+    unsafe { libc::signal(libc::SIGINT, previous) };
+}
+
+#[cfg(unix)]
+#[test]
+fn should_stop_waiting_on_ctrl_c() {
+    use crate::term::{interrupt, read_line_fd};
+    let _flag = INTERRUPT_FLAG.lock().unwrap();
+    let (read_end, write_end) = pipe_with(b"");
+    interrupt();
+    let err = read_line_fd(read_end, None, false).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::Interrupted);
+    interrupt();
+    let err = read_line_fd(read_end, Some(Duration::from_millis(10)), true).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+    unsafe {
+        libc::close(read_end);
+        libc::close(write_end);
+    }
+}
+
+#[test]
+fn should_decode_keys() {
+    use crate::editor::{read_key, Key};
+    let mut bytes: &[u8] = b"a\x1b[D\x1b[1;5C\x1bb\x1b[3~\x01\x17\x7f\r\xc3\xa9";
+    let mut keys = Vec::new();
+    while !bytes.is_empty() {
+        keys.push(read_key(&mut bytes).unwrap());
+    }
+    assert_eq!(
+        keys,
+        vec![
+            Key::Char('a'),
+            Key::Left,
+            Key::WordRight,
+            Key::WordLeft,
+            Key::Delete,
+            Key::Home,
+            Key::KillWordBack,
+            Key::Backspace,
+            Key::Enter,
+            Key::Char('é'),
+        ]
+    );
+}
+
+#[test]
+fn should_edit_line_with_keys() {
+    use crate::editor::{edit, Key, Session};
+    let keys = vec![
+        Key::Char('o'),
+        Key::Char('r'),
+        Key::Char('l'),
+        Key::Char('d'),
+        Key::Home,
+        Key::Char('w'),
+        Key::KillToStart,
+        Key::End,
+        Key::Char(' '),
+        Key::Yank,
+        Key::Home,
+        Key::Char('h'),
+        Key::Char('i'),
+        Key::Char(' '),
+        Key::Enter,
+    ];
+    let mut keys = keys.into_iter();
+    let out = Output::default();
+    let line = edit(|| Ok(keys.next().unwrap()), &out, &Session::default()).unwrap();
+    assert_eq!(line, "hi orld w\n");
+    assert!(out.text().ends_with("\r\n"));
+}
+
+#[test]
+fn should_kill_word_and_stop_editing() {
+    use crate::editor::{edit, Key, Session};
+    let mut keys = "one two".chars().map(Key::Char).collect::<Vec<_>>();
+    keys.extend([Key::KillWordBack, Key::Backspace, Key::Enter]);
+    let mut keys = keys.into_iter();
+    let line = edit(
+        || Ok(keys.next().unwrap()),
+        &Output::default(),
+        &Session::default(),
+    )
+    .unwrap();
+    assert_eq!(line, "one\n");
+
+    let session = Session {
+        ignore_interrupts: true,
+        ..Default::default()
+    };
+    let mut keys = vec![Key::Interrupt, Key::EndOfInput].into_iter();
+    let err = edit(|| Ok(keys.next().unwrap()), &Output::default(), &session).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+
+    let mut keys = vec![Key::Char('y'), Key::Interrupt].into_iter();
+    let err = edit(
+        || Ok(keys.next().unwrap()),
+        &Output::default(),
+        &Session::default(),
+    )
+    .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::Interrupted);
+}
+
+#[test]
+fn should_keep_history_without_duplicates() {
+    use crate::history::History;
+    let path = std::env::temp_dir().join(format!("jaw-history-{}/history", std::process::id()));
+    let mut history = History::at(&path).unwrap().with_limit(2);
+    history.add("host", "alpha\n").unwrap();
+    history.add("host", "beta").unwrap();
+    history.add("host", "alpha").unwrap();
+    history.add("host", "gamma").unwrap();
+    history.add("host", "  ").unwrap();
+    history.add("port", "8080").unwrap();
+    assert_eq!(history.entries("host"), ["alpha", "gamma"]);
+    assert!(history.entries("user").is_empty());
+
+    let history = History::at(&path).unwrap();
+    assert_eq!(history.entries("host"), ["alpha", "gamma"]);
+    assert_eq!(history.entries("port"), ["8080"]);
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn should_remember_accepted_answers() {
+    #[derive(Default)]
+    struct Remembering {
+        script: ScriptedReader,
+        remembered: RefCell<Vec<(String, String)>>,
+    }
+    impl Reader for Remembering {
+        fn read_string(&self) -> Result<String, std::io::Error> {
+            self.script.read_string()
+        }
+        fn remember(&self, prompt: &Prompt, answer: &str) {
+            let id = prompt.get_id().unwrap_or_default().to_string();
+            self.remembered.borrow_mut().push((id, answer.to_string()));
+        }
+    }
+    struct User;
+    impl crate::answers::AnswerSource for User {
+        fn answer(&self, id: &str) -> Option<String> {
+            (id == "user").then(|| "env".to_string())
+        }
+        fn name(&self, _: &str) -> String {
+            "user".to_string()
+        }
+    }
+    let reader = Remembering::default();
+    for answer in ["x", "80", "token", "me"] {
+        reader.script.push(answer);
+    }
+    let input = Input::new(reader)
+        .with_writer(Output::default())
+        .with_feedback(Feedback::Silent)
+        .with_source(User);
+    let port = Prompt::new("Port: ").id("port");
+    assert_eq!(input.ask(port, |s| s.parse::<u16>(), None).unwrap(), 80);
+    input
+        .prompt(Prompt::new("Token: ").id("token").no_history())
+        .unwrap();
+    input.prompt(Prompt::new("User: ").id("user")).unwrap();
+    input.prompt(Prompt::new("Name: ").id("name")).unwrap();
+    assert_eq!(
+        *input.reader().remembered.borrow(),
+        [
+            ("port".to_string(), "80".to_string()),
+            ("name".to_string(), "me".to_string())
+        ]
+    );
+}
+
+#[test]
+fn should_recall_history_with_arrows() {
+    use crate::editor::{edit, Key, Session};
+    let history = ["one".to_string(), "two".to_string()];
+    let session = Session {
+        history: &history,
+        ..Default::default()
+    };
+    let keys = vec![
+        Key::Char('t'),
+        Key::Up,
+        Key::Up,
+        Key::Up,
+        Key::Down,
+        Key::Down,
+        Key::Char('!'),
+        Key::Enter,
+    ];
+    let mut keys = keys.into_iter();
+    let line = edit(|| Ok(keys.next().unwrap()), &Output::default(), &session).unwrap();
+    assert_eq!(line, "t!\n");
+
+    let mut keys = vec![Key::Up, Key::Char('s'), Key::Enter].into_iter();
+    let line = edit(|| Ok(keys.next().unwrap()), &Output::default(), &session).unwrap();
+    assert_eq!(line, "twos\n");
+}
+
+#[test]
+fn should_complete_words_and_paths() {
+    use crate::completion::{common_prefix, Completer, Options, Paths, Words};
+    let words = Words::new(["status", "stash", "commit"]);
+    let completion = words.complete("git st");
+    assert_eq!(completion.start, 4);
+    assert_eq!(completion.candidates, ["status", "stash"]);
+    assert_eq!(common_prefix(&completion.candidates), "sta");
+
+    let options = Options::new(["Apple", "Apricot", "Banana"]);
+    assert_eq!(options.complete("ap").candidates, ["Apple", "Apricot"]);
+
+    let dir = std::env::temp_dir().join(format!("jaw-paths-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(dir.join("setup.txt"), "").unwrap();
+    std::fs::write(dir.join(".secret"), "").unwrap();
+    let typed = format!("open {}/s", dir.display());
+    let completion = Paths.complete(&typed);
+    assert_eq!(completion.start, 5);
+    assert_eq!(
+        completion.candidates,
+        [
+            format!("{}/setup.txt", dir.display()),
+            format!("{}/src/", dir.display())
+        ]
+    );
+    let hidden = Paths.complete(&format!("{}/.", dir.display()));
+    assert_eq!(hidden.candidates, [format!("{}/.secret", dir.display())]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn should_complete_on_tab() {
+    use crate::completion::Completion;
+    use crate::editor::{edit, Key, Session};
+    let completer = |line: &str| Completion {
+        start: 0,
+        candidates: ["deploy", "describe", "delete"]
+            .iter()
+            .filter(|c| c.starts_with(line))
+            .map(|c| c.to_string())
+            .collect(),
+    };
+    let session = Session {
+        prompt: "> ".to_string(),
+        completer: Some(&completer),
+        ..Default::default()
+    };
+    let keys = vec![
+        Key::Char('d'),
+        Key::Tab,
+        Key::Tab,
+        Key::Char('p'),
+        Key::Tab,
+        Key::Enter,
+    ];
+    let mut keys = keys.into_iter();
+    let out = Output::default();
+    let line = edit(|| Ok(keys.next().unwrap()), &out, &session).unwrap();
+    assert_eq!(line, "deploy\n");
+    assert!(out.text().contains("\r\ndeploy  describe  delete\r\n> "));
+}
+
+#[test]
+fn should_pass_completer_to_reader() {
+    use crate::completion::Words;
+    struct Completing;
+    impl Reader for Completing {
+        fn read_string(&self) -> Result<String, std::io::Error> {
+            unreachable!()
+        }
+        fn read_prompted(&self, prompt: &Prompt, _: &dyn Writer) -> Result<String, std::io::Error> {
+            let completer = prompt.get_completer().unwrap();
+            let completion = completer.complete(prompt.text().trim());
+            Ok(completion.candidates.join(","))
+        }
+    }
+    let input = Input::new(Completing)
+        .with_writer(Output::default())
+        .with_completer(Words::new(["alpha", "beta"]));
+    assert_eq!(input.prompt("a").unwrap(), "alpha");
+    let items = ["Banana", "Bread", "Cake"];
+    let err = input.select("B", &items, Some(1)).unwrap_err();
+    assert_eq!(err.attempt_errors(), ["no option matches 'banana,bread'"]);
+}
+
+#[test]
+fn should_match_fuzzy() {
+    use crate::fuzzy::{fuzzy_filter, fuzzy_match};
+    let m = fuzzy_match("fb", "feature/bar").unwrap();
+    assert_eq!(m.positions, [0, 8]);
+    assert!(fuzzy_match("bf", "feature/bar").is_none());
+    assert!(fuzzy_match("MAIN", "origin/main").is_some());
+    assert!(fuzzy_match("", "anything").is_some());
+
+    let branches = ["fix-bug", "feature/bar", "main", "foobar"];
+    let found: Vec<usize> = fuzzy_filter("bar", &branches)
+        .into_iter()
+        .map(|(i, _)| i)
+        .collect();
+    assert_eq!(found, [1, 3]);
+    let found: Vec<usize> = fuzzy_filter("fb", &branches)
+        .into_iter()
+        .map(|(i, _)| i)
+        .collect();
+    assert_eq!(found, [0, 1, 3]);
+}
+
+#[test]
+fn should_pick_with_fuzzy_finder() {
+    use crate::editor::{pick, Key};
+    let hosts: Vec<String> = (1..=300).map(|i| format!("host-{:03}", i)).collect();
+    let keys = vec![
+        Key::Char('2'),
+        Key::Char('5'),
+        Key::Backspace,
+        Key::Char('4'),
+        Key::Char('2'),
+        Key::Down,
+        Key::Enter,
+    ];
+    let mut keys = keys.into_iter();
+    let out = Output::default();
+    let index = pick(|| Ok(keys.next().unwrap()), &out, "Host: ", &hosts, false).unwrap();
+    assert_eq!(hosts[index], "host-242");
+    assert!(out.text().contains("\x1b[1m2\x1b[22m"));
+    assert!(out.text().ends_with("\rHost: host-242\x1b[K\x1b[J\r\n"));
+}
+
+#[test]
+fn should_fall_back_to_numbered_select() {
+    let script = ScriptedReader::new(["2"]);
+    let input = script.input();
+    let items = ["main", "develop"];
+    assert_eq!(
+        input.fuzzy_select("Branch: ", &items, None).unwrap(),
+        &"develop"
+    );
+    assert!(script.output().starts_with("1) main\n2) develop\n"));
+
+    struct Picking;
+    impl Reader for Picking {
+        fn read_string(&self) -> Result<String, std::io::Error> {
+            unreachable!()
+        }
+        fn pick(
+            &self,
+            _: &Prompt,
+            labels: &[String],
+            _: &dyn Writer,
+        ) -> Option<Result<usize, std::io::Error>> {
+            Some(Ok(labels.len() - 1))
+        }
+    }
+    let input = Input::new(Picking).with_writer(Output::default());
+    assert_eq!(
+        input.fuzzy_select("Branch: ", &items, None).unwrap(),
+        &"develop"
+    );
+    assert_eq!(input.writer().text(), "");
+}
+
+#[test]
+fn should_apply_input_settings_to_fuzzy_select() {
+    use crate::answers::MissingAnswer;
+    #[derive(Default)]
+    struct Slow {
+        timeouts: RefCell<Vec<Option<Duration>>>,
+    }
+    impl Reader for Slow {
+        fn read_string(&self) -> Result<String, std::io::Error> {
+            unreachable!()
+        }
+        fn pick(
+            &self,
+            prompt: &Prompt,
+            _: &[String],
+            _: &dyn Writer,
+        ) -> Option<Result<usize, std::io::Error>> {
+            self.timeouts.borrow_mut().push(prompt.get_timeout());
+            Some(Err(std::io::ErrorKind::TimedOut.into()))
+        }
+    }
+    let items = ["main", "develop"];
+    let input = Input::new(Slow::default())
+        .with_writer(Output::default())
+        .with_timeout(Duration::from_secs(5));
+    let branch = Prompt::new("Branch: ").default("dev");
+    assert_eq!(
+        input.fuzzy_select(branch, &items, None).unwrap(),
+        &"develop"
+    );
+    let err = input.fuzzy_select("Branch: ", &items, None).unwrap_err();
+    assert_eq!(&ErrorKind::Timeout, err.kind());
+    assert_eq!(
+        *input.reader().timeouts.borrow(),
+        [Some(Duration::from_secs(5)); 2]
+    );
+
+    std::env::set_var("JAW_PICK_BRANCH", "main");
+    let input = Input::new(Slow::default())
+        .with_writer(Output::default())
+        .with_env_prefix("JAW_PICK")
+        .with_missing_answer(MissingAnswer::Fail);
+    let branch = Prompt::new("Branch: ").id("branch");
+    assert_eq!(input.fuzzy_select(branch, &items, None).unwrap(), &"main");
+    let base = Prompt::new("Base: ").id("base");
+    let err = input.fuzzy_select(base, &items, None).unwrap_err();
+    assert_eq!(&ErrorKind::MissingAnswer, err.kind());
+    assert!(input.reader().timeouts.borrow().is_empty());
+    assert_eq!(input.writer().text(), "");
+}
+
+#[test]
+fn should_draw_answers_to_input_writer() {
+    struct Drawing;
+    impl Reader for Drawing {
+        fn read_string(&self) -> Result<String, std::io::Error> {
+            unreachable!()
+        }
+        fn read_prompted(&self, _: &Prompt, out: &dyn Writer) -> Result<String, std::io::Error> {
+            out.write_string("bob\n")?;
+            Ok("bob".to_string())
+        }
+        fn pick(
+            &self,
+            prompt: &Prompt,
+            labels: &[String],
+            out: &dyn Writer,
+        ) -> Option<Result<usize, std::io::Error>> {
+            let drawn = format!("{}{}\n", prompt.render(), labels[1]);
+            Some(out.write_string(&drawn).map(|_| 1))
+        }
+    }
+    let input = Input::new(Drawing).with_writer(Output::default());
+    assert_eq!(input.prompt("Name: ").unwrap(), "bob");
+    let items = ["main", "develop"];
+    assert_eq!(
+        input.fuzzy_select("Branch: ", &items, None).unwrap(),
+        &"develop"
+    );
+    assert_eq!(input.writer().text(), "Name: bob\nBranch: develop\n");
+}
+
+#[test]
+fn should_hint_default_and_history() {
+    use crate::hints::{DefaultHinter, HintContext, Hinter, HistoryHinter, Suggestions};
+    let history = ["deploy staging".to_string(), "deploy prod".to_string()];
+    let context = HintContext {
+        default: Some("describe"),
+        history: &history,
+    };
+    assert_eq!(HistoryHinter.hint("dep", &context).unwrap(), "loy prod");
+    assert_eq!(HistoryHinter.hint("deploy s", &context).unwrap(), "taging");
+    assert_eq!(DefaultHinter.hint("", &context).unwrap(), "describe");
+    assert!(DefaultHinter.hint("describe", &context).is_none());
+    assert_eq!(Suggestions.hint("des", &context).unwrap(), "cribe");
+    assert!(Suggestions.hint("x", &context).is_none());
+}
+
+#[test]
+fn should_accept_hint_with_right_arrow() {
+    use crate::editor::{edit, Key, Session};
+    use crate::hints::DefaultHinter;
+    let session = Session {
+        hinter: Some(&DefaultHinter),
+        default: Some("8080"),
+        ..Default::default()
+    };
+    let mut keys = vec![Key::Char('8'), Key::Right, Key::Enter].into_iter();
+    let out = Output::default();
+    let line = edit(|| Ok(keys.next().unwrap()), &out, &session).unwrap();
+    assert_eq!(line, "8080\n");
+    assert!(out.text().starts_with("\x1b[2m8080\x1b[22m\x1b[K\x1b[4D"));
+    assert!(out.text().contains("8\x1b[2m080\x1b[22m"));
+
+    let mut keys = vec![Key::Char('9'), Key::Right, Key::Enter].into_iter();
+    let out = Output::default();
+    let line = edit(|| Ok(keys.next().unwrap()), &out, &session).unwrap();
+    assert_eq!(line, "9\n");
+    assert!(out.text().ends_with("\x1b[1D9\x1b[K\r\n"));
 }
@@ -0,0 +1,304 @@
+//! # Transcripts
+//!
+//! a recorded session can be fed back to reproduce it,
+//! e.g. as a regression test for a reported bug
+//!
+//! a transcript has a line per answer: milliseconds since the unix
+//! epoch, the prompt id or `-` and the answer as it was read, separated
+//! by tabs, where tabs, line breaks and backslashes are escaped,
+//! a secret is recorded as `\*` in place of the answer
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::cli::{Prompt, Reader, Writer};
+
+/// stands for a secret, escaping never gives it
+const SECRET: &str = "\\*";
+
+/// a recorded answer
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub timestamp: u128,
+    pub id: Option<String>,
+    pub answer: String,
+    /// a secret read without recording it, the answer is empty
+    pub secret: bool,
+}
+
+impl Entry {
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\n",
+            self.timestamp,
+            self.id
+                .as_deref()
+                .map(escape)
+                .unwrap_or_else(|| "-".to_string()),
+            if self.secret {
+                SECRET.to_string()
+            } else {
+                escape(&self.answer)
+            }
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Entry> {
+        let mut parts = line.splitn(3, '\t');
+        let timestamp = parts.next()?.parse().ok()?;
+        let id = match parts.next()? {
+            "-" => None,
+            id => Some(unescape(id)),
+        };
+        let (answer, secret) = match parts.next()? {
+            SECRET => (String::new(), true),
+            answer => (unescape(answer), false),
+        };
+        Some(Entry {
+            timestamp,
+            id,
+            answer,
+            secret,
+        })
+    }
+}
+
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(s: &str) -> String {
+    let mut unescaped = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// a reader that writes every answer of the inner reader to a transcript
+///
+/// secrets are never recorded, only a placeholder is
+pub struct Recorder<R>
+where
+    R: Reader,
+{
+    inner: R,
+    transcript: RefCell<Box<dyn Write>>,
+}
+
+impl<R> Recorder<R>
+where
+    R: Reader,
+{
+    /// records to a new file, an existing one is truncated
+    pub fn create<P>(inner: R, path: P) -> Result<Recorder<R>, io::Error>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(Recorder::new(inner, File::create(path)?))
+    }
+
+    pub fn new<T>(inner: R, transcript: T) -> Recorder<R>
+    where
+        T: Write + 'static,
+    {
+        Recorder {
+            inner,
+            transcript: RefCell::new(Box::new(transcript)),
+        }
+    }
+
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    fn record(&self, id: Option<&str>, answer: &str, secret: bool) -> Result<(), io::Error> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or_default();
+        let entry = Entry {
+            timestamp,
+            id: id.map(|id| id.to_string()),
+            answer: answer.to_string(),
+            secret,
+        };
+        let mut transcript = self.transcript.borrow_mut();
+        transcript.write_all(entry.to_line().as_bytes())?;
+        transcript.flush()
+    }
+}
+
+impl<R> Reader for Recorder<R>
+where
+    R: Reader,
+{
+    fn read_string(&self) -> Result<String, io::Error> {
+        let answer = self.inner.read_string()?;
+        self.record(None, &answer, false)?;
+        Ok(answer)
+    }
+
+    fn read_secret(&self) -> Result<String, io::Error> {
+        let secret = self.inner.read_secret()?;
+        self.record(None, "", true)?;
+        Ok(secret)
+    }
+
+    fn read_prompted(&self, prompt: &Prompt, out: &dyn Writer) -> Result<String, io::Error> {
+        let answer = self.inner.read_prompted(prompt, out)?;
+        self.record(prompt.get_id(), &answer, false)?;
+        Ok(answer)
+    }
+
+    fn read_string_timeout(&self, timeout: Duration) -> Result<String, io::Error> {
+        let answer = self.inner.read_string_timeout(timeout)?;
+        self.record(None, &answer, false)?;
+        Ok(answer)
+    }
+
+    /// the picked item is recorded by its number,
+    /// which `Input::select` takes on replay
+    fn pick(
+        &self,
+        prompt: &Prompt,
+        labels: &[String],
+        out: &dyn Writer,
+    ) -> Option<Result<usize, io::Error>> {
+        let picked = self.inner.pick(prompt, labels, out)?.and_then(|index| {
+            self.record(prompt.get_id(), &(index + 1).to_string(), false)?;
+            Ok(index)
+        });
+        Some(picked)
+    }
+
+    fn timed_out(&self, prompt: &Prompt, default: &str) -> Result<(), io::Error> {
+        self.inner.timed_out(prompt, default)?;
+        self.record(prompt.get_id(), default, false)
+    }
+
+    fn remember(&self, prompt: &Prompt, answer: &str) {
+        self.inner.remember(prompt, answer)
+    }
+}
+
+/// a reader that answers with a recorded transcript
+///
+/// an answer recorded with an id is only given to a prompt with
+/// the same id, after the last answer the input is closed,
+/// reading a secret fails as the transcript doesn't have it
+pub struct Replay {
+    entries: RefCell<VecDeque<Entry>>,
+}
+
+impl Replay {
+    pub fn open<P>(path: P) -> Result<Replay, io::Error>
+    where
+        P: AsRef<Path>,
+    {
+        Replay::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn parse(transcript: &str) -> Result<Replay, io::Error> {
+        let mut entries = VecDeque::new();
+        for (n, line) in transcript.lines().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+            let entry = Entry::from_line(line).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {} of the transcript is malformed", n + 1),
+                )
+            })?;
+            entries.push_back(entry);
+        }
+        Ok(Replay {
+            entries: RefCell::new(entries),
+        })
+    }
+
+    /// answers not given yet
+    pub fn remaining(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    fn next(&self, id: Option<&str>, secret: bool) -> Result<String, io::Error> {
+        let mut entries = self.entries.borrow_mut();
+        let Some(entry) = entries.front() else {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "the transcript is over",
+            ));
+        };
+        if entry.secret != secret {
+            let msg = if secret {
+                "the transcript has an answer, not a secret"
+            } else {
+                "the transcript has a secret, not an answer"
+            };
+            return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+        }
+        if let (Some(expected), Some(id)) = (entry.id.as_deref(), id) {
+            if expected != id {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "the transcript has an answer for '{}', not for '{}'",
+                        expected, id
+                    ),
+                ));
+            }
+        }
+        let answer = entries
+            .pop_front()
+            .map(|entry| entry.answer)
+            .unwrap_or_default();
+        if secret {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "secrets aren't recorded in transcripts",
+            ));
+        }
+        Ok(answer)
+    }
+}
+
+impl Reader for Replay {
+    fn read_string(&self) -> Result<String, io::Error> {
+        self.next(None, false)
+    }
+
+    fn read_secret(&self) -> Result<String, io::Error> {
+        self.next(None, true)
+    }
+
+    fn read_prompted(&self, prompt: &Prompt, _out: &dyn Writer) -> Result<String, io::Error> {
+        self.next(prompt.get_id(), false)
+    }
+}
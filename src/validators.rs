@@ -0,0 +1,136 @@
+//! # Claims
+//!
+//! reusable claims for `Claimy` demands and `Input::ask`,
+//! every claim reports a readable message on failure
+
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// the input has at least one non whitespace character
+pub fn non_empty() -> impl Fn(&str) -> Result<(), String> {
+    |s| {
+        if s.trim().is_empty() {
+            Err("can't be empty".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// the input is at least `min` characters long
+pub fn min_len(min: usize) -> impl Fn(&str) -> Result<(), String> {
+    move |s| {
+        if s.chars().count() < min {
+            Err(format!("should be at least {} characters long", min))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// the input is at most `max` characters long
+pub fn max_len(max: usize) -> impl Fn(&str) -> Result<(), String> {
+    move |s| {
+        if s.chars().count() > max {
+            Err(format!("should be at most {} characters long", max))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// the input matches the regular expression
+///
+/// # Panics
+///
+/// if the pattern isn't a valid regular expression
+#[cfg(feature = "regex")]
+pub fn matches_regex(pattern: &str) -> impl Fn(&str) -> Result<(), String> {
+    let re = regex::Regex::new(pattern).expect("invalid pattern");
+    move |s| {
+        if re.is_match(s) {
+            Ok(())
+        } else {
+            Err(format!("should match {}", re.as_str()))
+        }
+    }
+}
+
+/// the input is a value within the range, the value is returned
+pub fn in_range<T>(range: RangeInclusive<T>) -> impl Fn(&str) -> Result<T, String>
+where
+    T: FromStr + PartialOrd + Display,
+    T::Err: Display,
+{
+    move |s| {
+        let value = s.trim().parse::<T>().map_err(|err| err.to_string())?;
+        if range.contains(&value) {
+            Ok(value)
+        } else {
+            Err(format!(
+                "should be from {} to {}",
+                range.start(),
+                range.end()
+            ))
+        }
+    }
+}
+
+/// the input is one of the options, the matching option is returned
+pub fn one_of(options: &[&str]) -> impl Fn(&str) -> Result<String, String> {
+    let options: Vec<String> = options.iter().map(|o| o.to_string()).collect();
+    move |s| match options.iter().find(|o| *o == s) {
+        Some(option) => Ok(option.clone()),
+        None => Err(format!("should be one of: {}", options.join(", "))),
+    }
+}
+
+/// both claims are satisfied, the value of the second one is returned
+pub fn and<A, B, Ta, Tb, Ea, Eb>(a: A, b: B) -> impl Fn(&str) -> Result<Tb, String>
+where
+    A: Fn(&str) -> Result<Ta, Ea>,
+    B: Fn(&str) -> Result<Tb, Eb>,
+    Ea: Display,
+    Eb: Display,
+{
+    move |s| {
+        a(s).map_err(|err| err.to_string())?;
+        b(s).map_err(|err| err.to_string())
+    }
+}
+
+/// any of the claims is satisfied, the first one is tried first
+pub fn or<A, B, T, Ea, Eb>(a: A, b: B) -> impl Fn(&str) -> Result<T, String>
+where
+    A: Fn(&str) -> Result<T, Ea>,
+    B: Fn(&str) -> Result<T, Eb>,
+    Ea: Display,
+    Eb: Display,
+{
+    move |s| match a(s) {
+        Ok(value) => Ok(value),
+        Err(ea) => b(s).map_err(|eb| format!("{} or {}", ea, eb)),
+    }
+}
+
+/// the claim isn't satisfied, `msg` is reported otherwise
+pub fn not<A, T, E>(a: A, msg: &str) -> impl Fn(&str) -> Result<(), String>
+where
+    A: Fn(&str) -> Result<T, E>,
+{
+    let msg = msg.to_string();
+    move |s| match a(s) {
+        Ok(_) => Err(msg.clone()),
+        Err(_) => Ok(()),
+    }
+}
+
+/// replaces the error of the claim
+pub fn map_err<A, T, E, M, Em>(a: A, map: M) -> impl Fn(&str) -> Result<T, Em>
+where
+    A: Fn(&str) -> Result<T, E>,
+    M: Fn(E) -> Em,
+{
+    move |s| a(s).map_err(&map)
+}
@@ -0,0 +1,173 @@
+//! # Wizard
+//!
+//! asks a sequence of questions with a chance to go back
+//! and to review the answers before they are accepted
+
+use std::fmt::Display;
+
+use crate::cli::{ErrorKind, Input, InputReadError, Prompt, Reader, Writer};
+
+/// an answer that returns to the previous step
+pub const BACK: &str = ":back";
+/// an answer that leaves the wizard
+pub const CANCEL: &str = ":cancel";
+/// an empty answer to a step answered before, where just
+/// pressing enter keeps the previous answer
+pub const CLEAR: &str = ":clear";
+/// the id of the review prompt, an answer source can confirm
+/// the answers with an empty answer
+pub const REVIEW: &str = "review";
+
+type Check<'a> = Box<dyn Fn(&str) -> Result<(), String> + 'a>;
+
+enum Reply {
+    Answer(String),
+    Back,
+    Cancel,
+}
+
+enum Review {
+    Done,
+    Edit(usize),
+    Cancel,
+}
+
+/// ordered steps asked through an `Input`
+///
+/// typing `:back` returns to the previous step and `:cancel` leaves
+/// the wizard with `ErrorKind::Cancelled`, after the last step all
+/// answers are listed and any of them can be changed by its number,
+/// `:clear` replaces an answer with an empty one
+pub struct Wizard<'a> {
+    steps: Vec<(Prompt<'a>, Check<'a>)>,
+}
+
+impl<'a> Wizard<'a> {
+    pub fn new() -> Wizard<'a> {
+        Wizard { steps: Vec::new() }
+    }
+
+    /// adds a step answered when the claim is satisfied
+    pub fn step<F, Ft, Fe>(mut self, prompt: impl Into<Prompt<'a>>, claim: F) -> Self
+    where
+        F: Fn(&str) -> Result<Ft, Fe> + 'a,
+        Fe: Display,
+    {
+        let check = move |s: &str| claim(s).map(|_| ()).map_err(|err| err.to_string());
+        self.steps.push((prompt.into(), Box::new(check)));
+        self
+    }
+
+    /// runs the steps and gives the answers in order of the steps
+    pub fn run<R, W>(&self, input: &Input<R, W>) -> Result<Vec<String>, InputReadError>
+    where
+        R: Reader,
+        W: Writer,
+    {
+        let mut answers: Vec<String> = Vec::new();
+        let mut step = 0;
+        while step < self.steps.len() {
+            match self.ask_step(input, step, answers.get(step))? {
+                Reply::Answer(answer) => {
+                    // later answers stay to be offered as defaults
+                    match answers.get_mut(step) {
+                        Some(previous) => *previous = answer,
+                        None => answers.push(answer),
+                    }
+                    step += 1;
+                }
+                Reply::Back => step = step.saturating_sub(1),
+                Reply::Cancel => return Err(cancelled()),
+            }
+        }
+        loop {
+            input.writer().write_string(&self.summary(&answers))?;
+            match self.review(input)? {
+                Review::Done => return Ok(answers),
+                Review::Edit(step) => match self.ask_step(input, step, answers.get(step))? {
+                    Reply::Answer(answer) => answers[step] = answer,
+                    Reply::Back => {}
+                    Reply::Cancel => return Err(cancelled()),
+                },
+                Review::Cancel => return Err(cancelled()),
+            }
+        }
+    }
+
+    /// a previous answer, if any, is offered as the default,
+    /// `:clear` gives an empty answer instead
+    fn ask_step<R, W>(
+        &self,
+        input: &Input<R, W>,
+        step: usize,
+        previous: Option<&String>,
+    ) -> Result<Reply, InputReadError>
+    where
+        R: Reader,
+        W: Writer,
+    {
+        let (prompt, check) = &self.steps[step];
+        let prompt = match previous {
+            Some(previous) => prompt.clone().default(previous),
+            None => prompt.clone(),
+        };
+        input.ask(
+            prompt,
+            |s: &str| match s.trim() {
+                BACK => Ok(Reply::Back),
+                CANCEL => Ok(Reply::Cancel),
+                CLEAR => check("").map(|_| Reply::Answer(String::new())),
+                _ => check(s).map(|_| Reply::Answer(s.to_string())),
+            },
+            None,
+        )
+    }
+
+    fn summary(&self, answers: &[String]) -> String {
+        let mut summary = String::new();
+        for (i, ((prompt, _), answer)) in self.steps.iter().zip(answers).enumerate() {
+            let label = prompt.text().trim_end().trim_end_matches(':');
+            summary.push_str(&format!("{}) {}: {}\n", i + 1, label, answer));
+        }
+        summary
+    }
+
+    fn review<R, W>(&self, input: &Input<R, W>) -> Result<Review, InputReadError>
+    where
+        R: Reader,
+        W: Writer,
+    {
+        let count = self.steps.len();
+        let prompt =
+            Prompt::new("Type a number to change an answer or press Enter to confirm: ").id(REVIEW);
+        // an answer source would give the same edit again and again
+        let answered = input.source_answer(&prompt).is_some();
+        input.ask(
+            prompt,
+            |s: &str| match s.trim() {
+                "" => Ok(Review::Done),
+                CANCEL => Ok(Review::Cancel),
+                _ if answered => Err("only an empty answer or :cancel is expected".to_string()),
+                BACK => match count.checked_sub(1) {
+                    Some(last) => Ok(Review::Edit(last)),
+                    None => Err("there are no answers to go back to".to_string()),
+                },
+                s => match s.parse::<usize>() {
+                    Ok(n) if (1..=count).contains(&n) => Ok(Review::Edit(n - 1)),
+                    _ => Err(format!("please type a number from 1 to {}", count)),
+                },
+            },
+            None,
+        )
+    }
+}
+
+impl Default for Wizard<'_> {
+    fn default() -> Self {
+        Wizard::new()
+    }
+}
+
+fn cancelled() -> InputReadError {
+    InputReadError::new("the wizard was cancelled".to_string(), ErrorKind::Cancelled)
+}
@@ -11,7 +11,37 @@ fn construct_type() -> Result<(), Box<dyn Error>> {
 #[ignore = "needs user input"]
 fn try_to_read_user_input() -> Result<(), Box<dyn Error>> {
     let input = Input::new(stdin());
-    println!("please type '100'");
-    let _input_result = input.read();
+    let _input_result = input.prompt("please type '100': ");
     Ok(())
 }
+
+#[cfg(all(feature = "derive", feature = "testing"))]
+mod form {
+    use jaw::cli::Form;
+    use jaw::testing::ScriptedReader;
+    use jaw::validators::non_empty;
+
+    #[derive(Form, Debug, PartialEq)]
+    struct Server {
+        #[form(prompt = "Host: ", validator = non_empty())]
+        host: String,
+        #[form(prompt = "Port: ", default = "8080", attempts = 2)]
+        port: u16,
+        verbose: bool,
+    }
+
+    #[test]
+    fn should_fill_derived_form() {
+        let script = ScriptedReader::new(["example.com\n", "http\n", "\n", "true\n"]);
+        let server = Server::fill(&script.input()).unwrap();
+        assert_eq!(
+            server,
+            Server {
+                host: "example.com".to_string(),
+                port: 8080,
+                verbose: true,
+            }
+        );
+        script.assert_consumed();
+    }
+}